@@ -1,13 +1,19 @@
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    spanned::Spanned, GenericArgument, Ident, PathArguments, Type, Error
+    spanned::Spanned, GenericArgument, Ident, PathArguments, Type, Visibility
 };
 
 use super::fields::FieldInfo;
+use super::options::{BuilderOptions, OnDuplicate};
 
 type Option<T> = std::option::Option<T>;
 
-pub fn get_builder_struct(fields: &Vec<FieldInfo>, name: &Ident) -> proc_macro2::TokenStream {
+pub fn get_builder_struct(
+    fields: &Vec<FieldInfo>,
+    name: &Ident,
+    vis: &Visibility,
+    options: &BuilderOptions,
+) -> proc_macro2::TokenStream {
 
     let recurse = fields.iter().map(|f| {
         let name = f.name;
@@ -23,37 +29,86 @@ pub fn get_builder_struct(fields: &Vec<FieldInfo>, name: &Ident) -> proc_macro2:
             }
         }
     });
+    let derives = if options.derives.is_empty() {
+        proc_macro2::TokenStream::new()
+    } else {
+        let derives = &options.derives;
+        quote! { #[derive(#(#derives),*)] }
+    };
+    let set_flags = fields
+        .iter()
+        .filter_map(|f| set_flag(f, options))
+        .map(|flag| {
+            quote! { #flag: bool, }
+        });
+    let struct_attrs = &options.struct_attrs;
+    let duplicates = if records_duplicates(fields, options) {
+        quote! { __duplicates: std::vec::Vec<&'static str>, }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
     quote! {
-        type String = std::string::String;
-        pub struct #name {
+        #derives
+        #(#[#struct_attrs])*
+        #vis struct #name {
             #(#recurse)*
+            #(#set_flags)*
+            #duplicates
         }
     }
-    .into()
+}
+
+fn is_set_once(f: &FieldInfo, options: &BuilderOptions) -> bool {
+    f.set_once || options.set_once
+}
+
+/// Builder field recording whether the whole-collection setter of a
+/// `set_once` field with `each` has run. Its slot alone cannot tell, since the
+/// per-item setter fills it too.
+fn set_flag(f: &FieldInfo, options: &BuilderOptions) -> Option<Ident> {
+    match (&f.each, f.name) {
+        (Option::Some(_), Option::Some(name)) if is_set_once(f, options) => {
+            Option::Some(format_ident!("__{}_set", name))
+        }
+        _ => Option::None,
+    }
+}
+
+/// Whether the builder needs a slot for fields assigned more than once, which
+/// `build()` then reports instead of panicking in the setter.
+fn records_duplicates(fields: &[FieldInfo], options: &BuilderOptions) -> bool {
+    options.on_duplicate == OnDuplicate::Error && fields.iter().any(|f| is_set_once(f, options))
 }
 
 pub fn init_builder_struct(
     fields: &Vec<FieldInfo>,
     builder_name: &Ident,
+    options: &BuilderOptions,
 ) -> proc_macro2::TokenStream {
     let recurse = fields.iter().map(|f| {
         let name = f.name;
-        if f.each.is_some() {
-            quote_spanned! { name.span()=>
-                #name: std::option::Option::Some(vec![]),
-            }
-        } else {
-            quote_spanned! { name.span()=>
-                #name: std::option::Option::None,
-            }
+        quote_spanned! { name.span()=>
+            #name: std::option::Option::None,
         }
     });
+    let set_flags = fields
+        .iter()
+        .filter_map(|f| set_flag(f, options))
+        .map(|flag| {
+            quote! { #flag: false, }
+        });
+    let duplicates = if records_duplicates(fields, options) {
+        quote! { __duplicates: std::vec::Vec::new(), }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
     quote! {
         #builder_name {
             #(#recurse)*
+            #(#set_flags)*
+            #duplicates
         }
     }
-    .into()
 }
 
 fn get_nested_type(ty: &Type) -> Type {
@@ -72,54 +127,174 @@ fn get_nested_type(ty: &Type) -> Type {
     }
 }
 
-fn gen_setters(fields: &Vec<FieldInfo>) -> proc_macro2::TokenStream {
+/// Stores `value` in the field's slot, refusing a second assignment of a
+/// `set_once` field in the way selected by `on_duplicate`.
+fn gen_assign(
+    f: &FieldInfo,
+    value: &proc_macro2::TokenStream,
+    options: &BuilderOptions,
+) -> proc_macro2::TokenStream {
+    let name = f.name;
+    let (is_set, mark_set) = match set_flag(f, options) {
+        Option::Some(flag) => (quote!(self.#flag), quote!(self.#flag = true;)),
+        Option::None => (
+            quote!(self.#name.is_some()),
+            proc_macro2::TokenStream::new(),
+        ),
+    };
+    let assign = quote! {
+        #mark_set
+        self.#name = std::option::Option::Some(#value);
+        self
+    };
+    if !is_set_once(f, options) {
+        return assign;
+    }
+    let name_string = name.clone().unwrap().to_string();
+    match options.on_duplicate {
+        OnDuplicate::Panic => quote! {
+            if #is_set {
+                std::panic!("field `{}` set more than once", #name_string);
+            }
+            #assign
+        },
+        OnDuplicate::Error => quote! {
+            if #is_set {
+                self.__duplicates.push(#name_string);
+                return self;
+            }
+            #assign
+        },
+    }
+}
+
+/// By-value setter for `#[builder(const)]` builders.
+///
+/// Overwriting a slot would have to drop the previous value, which a `const fn`
+/// cannot do for most types, so every field of a const builder is set-once. The
+/// replaced slot is then known to be `None` and can be forgotten.
+fn gen_const_setter(f: &FieldInfo, arg_ty: &dyn quote::ToTokens) -> proc_macro2::TokenStream {
+    let name = f.name;
+    let message = format!("field `{}` set more than once", name.clone().unwrap());
+    quote! {
+        #[track_caller]
+        pub const fn #name(mut self, #name: #arg_ty) -> Self {
+            if self.#name.is_some() {
+                std::panic!(#message);
+            }
+            std::mem::forget(std::mem::replace(
+                &mut self.#name,
+                std::option::Option::Some(#name),
+            ));
+            self
+        }
+    }
+}
+
+fn gen_setter(
+    f: &FieldInfo,
+    arg_ty: &dyn quote::ToTokens,
+    options: &BuilderOptions,
+) -> proc_macro2::TokenStream {
+    if options.is_const {
+        return gen_const_setter(f, arg_ty);
+    }
+    let name = f.name;
+    let body = gen_assign(f, &quote!(#name), options);
+    let track_caller = if is_set_once(f, options) && options.on_duplicate == OnDuplicate::Panic {
+        quote!(#[track_caller])
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    quote! {
+        #track_caller
+        pub fn #name(&mut self, #name: #arg_ty) -> &mut Self {
+            #body
+        }
+    }
+}
+
+/// Deprecated setters for the field's former names, forwarding to `#name`.
+fn gen_aliases(
+    f: &FieldInfo,
+    arg_ty: &dyn quote::ToTokens,
+    options: &BuilderOptions,
+) -> proc_macro2::TokenStream {
+    let name = f.name;
+    let recurse = f.aliases.iter().map(|alias| {
+        let alias_name = &alias.name;
+        let note = match &alias.note {
+            Option::Some(note) => note.value(),
+            Option::None => format!("use `{}` instead", name.clone().unwrap()),
+        };
+        let deprecated = match &alias.since {
+            Option::Some(since) => quote!(#[deprecated(since = #since, note = #note)]),
+            Option::None => quote!(#[deprecated(note = #note)]),
+        };
+        if options.is_const {
+            quote_spanned! { alias_name.span()=>
+                #deprecated
+                #[track_caller]
+                pub const fn #alias_name(self, #alias_name: #arg_ty) -> Self {
+                    self.#name(#alias_name)
+                }
+            }
+        } else {
+            quote_spanned! { alias_name.span()=>
+                #deprecated
+                #[track_caller]
+                pub fn #alias_name(&mut self, #alias_name: #arg_ty) -> &mut Self {
+                    self.#name(#alias_name)
+                }
+            }
+        }
+    });
+    quote! {
+        #(#recurse)*
+    }
+}
+
+fn gen_setters(fields: &Vec<FieldInfo>, options: &BuilderOptions) -> proc_macro2::TokenStream {
     let recurse = fields.iter().map(|f| {
         let name = f.name;
         let ty = f.ty;
         let is_optional = f.is_optional;
         let inner = &f.inner;
         if is_optional {
+            let setter = gen_setter(f, inner, options);
+            let aliases = gen_aliases(f, inner, options);
             quote! {
-                pub fn #name(&mut self, #name: #inner) -> &mut Self {
-                    self.#name = std::option::Option::Some(#name);
-                    self
-                }
+                #setter
+                #aliases
             }
         } else {
-            match f.each.clone() {
-                Option::Some((ident, str_lit)) => {
-                    if ident != "each" {
-                        return Error::new(ident.span(), r#"expected `builder(each = "...")`"#).into_compile_error();
-                    }
+            match &f.each {
+                Option::Some(str_lit) => {
                     let each_name = str_lit.value();
                     let each_id = Ident::new(&each_name, name.span());
                     let nested_type = get_nested_type(ty);
-                    let outer_fn = if each_name != name.clone().unwrap().to_string() {
-                        quote! {
-                            pub fn #name(&mut self, #name: #ty) -> &mut Self {
-                                self.#name = std::option::Option::Some(#name);
-                                self
-                            }
-                        }
+                    let (outer_fn, aliases) = if name.clone().unwrap() != each_name {
+                        (gen_setter(f, ty, options), gen_aliases(f, ty, options))
                     } else {
-                        proc_macro2::TokenStream::new()
+                        (proc_macro2::TokenStream::new(), gen_aliases(f, &nested_type, options))
                     };
                     quote! {
                         pub fn #each_id(&mut self, #each_id: #nested_type) -> &mut Self {
-                            if let std::option::Option::Some(ref mut v) = self.#name {
-                                v.push(#each_id);
-                            }
+                            self.#name
+                                .get_or_insert_with(std::default::Default::default)
+                                .push(#each_id);
                             self
                         }
                         #outer_fn
+                        #aliases
                     }
                 }
                 Option::None => {
+                    let setter = gen_setter(f, ty, options);
+                    let aliases = gen_aliases(f, ty, options);
                     quote! {
-                        pub fn #name(&mut self, #name: #ty) -> &mut Self {
-                            self.#name = std::option::Option::Some(#name);
-                            self
-                        }
+                        #setter
+                        #aliases
                     }
                 }
             }
@@ -140,6 +315,10 @@ fn gen_build(fields: &Vec<FieldInfo>) -> proc_macro2::TokenStream {
             quote_spanned! { name.span()=>
                 #name: self.#name.as_ref().cloned(),
             }
+        } else if f.each.is_some() {
+            quote_spanned! { name.span()=>
+                #name: self.#name.clone().unwrap_or_default(),
+            }
         } else {
             quote_spanned! { name.span()=>
                 #name: match &self.#name {
@@ -155,19 +334,65 @@ fn gen_build(fields: &Vec<FieldInfo>) -> proc_macro2::TokenStream {
     }
 }
 
+/// `const fn build_const()`, which panics during constant evaluation when a
+/// required field is missing. Slots are emptied with `take()` and the builder
+/// forgotten so that no destructor has to run.
+fn gen_build_const(fields: &[FieldInfo], struct_name: &Ident) -> proc_macro2::TokenStream {
+    let unwrap_build = fields.iter().map(|f| {
+        let name = f.name;
+        let error_message = format!("{} not present", name.clone().unwrap());
+        if f.is_optional {
+            quote_spanned! { name.span()=>
+                #name: self.#name.take(),
+            }
+        } else {
+            quote_spanned! { name.span()=>
+                #name: self.#name.take().expect(#error_message),
+            }
+        }
+    });
+
+    quote! {
+        pub const fn build_const(mut self) -> #struct_name {
+            let s = #struct_name {
+                #(#unwrap_build)*
+            };
+            std::mem::forget(self);
+            s
+        }
+    }
+}
+
 pub fn impl_builder(
     fields: &Vec<FieldInfo>,
     builder_name: &Ident,
     struct_name: &Ident,
+    options: &BuilderOptions,
 ) -> proc_macro2::TokenStream {
-    let setters = gen_setters(fields);
+    let setters = gen_setters(fields, options);
     let unwrap_build = gen_build(fields);
+    let check_duplicates = if records_duplicates(fields, options) {
+        quote! {
+            if let std::option::Option::Some(name) = self.__duplicates.first() {
+                return Err(std::format!("field `{}` set more than once", name).into());
+            }
+        }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    let build_const = if options.is_const {
+        gen_build_const(fields, struct_name)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
 
     quote! {
         impl #builder_name {
             #setters
+            #build_const
 
             pub fn build(&mut self) -> std::result::Result<#struct_name, std::boxed::Box<dyn std::error::Error>> {
+                #check_duplicates
                 let s = #struct_name {
                     #unwrap_build
                 };
@@ -175,5 +400,4 @@ pub fn impl_builder(
             }
         }
     }
-    .into()
 }
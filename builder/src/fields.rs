@@ -1,6 +1,6 @@
 use syn::{
-    Data, Fields, GenericArgument, Ident, Lit, LitStr, Meta, NestedMeta, PathArguments,
-    PathSegment, Type,
+    punctuated::Punctuated, spanned::Spanned, Attribute, Data, Error, Fields, GenericArgument,
+    Ident, Lit, LitStr, Meta, NestedMeta, PathArguments, PathSegment, Token, Type,
 };
 
 type Option<T> = std::option::Option<T>;
@@ -10,7 +10,16 @@ pub struct FieldInfo<'a> {
     pub ty: &'a Type,
     pub is_optional: bool,
     pub inner: Option<Ident>,
-    pub each: Option<(Ident, LitStr)>,
+    pub each: Option<LitStr>,
+    pub set_once: bool,
+    pub aliases: Vec<Alias>,
+}
+
+/// A deprecated setter name that forwards to the field's current setter.
+pub struct Alias {
+    pub name: Ident,
+    pub since: Option<LitStr>,
+    pub note: Option<LitStr>,
 }
 
 
@@ -34,27 +43,94 @@ pub fn first_generic_arg(args: &PathArguments) -> Option<&PathSegment> {
     }
 }
 
-fn match_meta(m: Meta) -> (Ident, LitStr) {
+/// Field-level `#[builder(...)]` settings.
+#[derive(Default)]
+struct FieldAttrs {
+    each: Option<LitStr>,
+    set_once: bool,
+    aliases: Vec<Alias>,
+}
+
+fn unrecognized(span: proc_macro2::Span) -> Error {
+    Error::new(span, r#"expected `builder(each = "...")`"#)
+}
+
+fn lit_str(lit: &Lit) -> syn::Result<&LitStr> {
+    match lit {
+        Lit::Str(s) => Ok(s),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn parse_alias(nested: &Punctuated<NestedMeta, Token![,]>) -> syn::Result<Alias> {
+    let mut name = Option::None;
+    let mut since = Option::None;
+    let mut note = Option::None;
+    for meta in nested {
+        let nv = match meta {
+            NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+            _ => return Err(Error::new_spanned(meta, r#"expected `key = "..."`"#)),
+        };
+        let value = lit_str(&nv.lit)?;
+        if nv.path.is_ident("name") {
+            name = Option::Some(value.parse()?);
+        } else if nv.path.is_ident("since") {
+            since = Option::Some(value.clone());
+        } else if nv.path.is_ident("note") {
+            note = Option::Some(value.clone());
+        } else {
+            return Err(Error::new_spanned(
+                &nv.path,
+                "expected one of `name`, `since`, `note`",
+            ));
+        }
+    }
+    match name {
+        Option::Some(name) => Ok(Alias { name, since, note }),
+        Option::None => Err(Error::new_spanned(nested, r#"missing `name = "..."`"#)),
+    }
+}
+
+fn match_meta(attrs: &mut FieldAttrs, m: &Meta) -> syn::Result<()> {
+    let ident = match m.path().get_ident() {
+        Option::Some(ident) => ident,
+        Option::None => return Err(unrecognized(m.path().span())),
+    };
     match m {
-        Meta::List(l) => match l.nested.first().unwrap() {
-            NestedMeta::Meta(m) => match_meta(m.to_owned()),
-            _ => unimplemented!(),
-        },
-        Meta::NameValue(nv) => {
-            let path = nv.path;
-            let lit = nv.lit;
-            let ident = path.segments.first().unwrap().ident.clone();
-            let each_name = match lit {
-                Lit::Str(s) => (ident, s),
-                _ => unimplemented!(),
-            };
-            each_name
+        Meta::NameValue(nv) if ident == "each" => {
+            attrs.each = Option::Some(lit_str(&nv.lit)?.clone());
+        }
+        Meta::Path(_) if ident == "set_once" => attrs.set_once = true,
+        Meta::NameValue(nv) if ident == "alias" => attrs.aliases.push(Alias {
+            name: lit_str(&nv.lit)?.parse()?,
+            since: Option::None,
+            note: Option::None,
+        }),
+        Meta::List(l) if ident == "alias" => attrs.aliases.push(parse_alias(&l.nested)?),
+        _ => return Err(unrecognized(ident.span())),
+    }
+    Ok(())
+}
+
+fn parse_field_attrs(field_attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field_attrs.iter().filter(|a| a.path.is_ident("builder")) {
+        match attr.parse_meta()? {
+            Meta::List(l) => {
+                for nested in &l.nested {
+                    match nested {
+                        NestedMeta::Meta(m) => match_meta(&mut attrs, m)?,
+                        NestedMeta::Lit(lit) => return Err(unrecognized(lit.span())),
+                    }
+                }
+            }
+            m => return Err(unrecognized(m.span())),
         }
-        _ => unimplemented!(),
     }
+    Ok(attrs)
 }
 
-pub fn parse_fields(data: &Data) -> Vec<FieldInfo> {
+pub fn parse_fields(data: &Data) -> syn::Result<Vec<FieldInfo<'_>>> {
     match *data {
         Data::Struct(ref data) => match data.fields {
             // Fields::Named(ref fields) => fields.clone(),
@@ -64,32 +140,25 @@ pub fn parse_fields(data: &Data) -> Vec<FieldInfo> {
                 .map(|f| {
                     let name = &f.ident;
                     let ty = &f.ty;
-                    let first_path_segment = first_path_segment(ty).unwrap();
-                    let is_optional = first_path_segment.ident.to_string() == "Option";
-                    let inner = if is_optional {
-                        let ident = &first_generic_arg(&first_path_segment.arguments)
-                            .unwrap()
-                            .ident;
-                        Option::Some(ident.to_owned())
-                    } else {
-                        Option::None
-                    };
-
-                    let each_name = match f.attrs.is_empty() {
-                        false => {
-                            let n = match_meta(f.attrs.first().unwrap().parse_meta().unwrap());
-                            Option::Some(n)
+                    let inner = match first_path_segment(ty) {
+                        Option::Some(segment) if segment.ident == "Option" => {
+                            first_generic_arg(&segment.arguments).map(|s| s.ident.to_owned())
                         }
-                        true => Option::None,
+                        _ => Option::None,
                     };
+                    let is_optional = inner.is_some();
 
-                    FieldInfo {
+                    let attrs = parse_field_attrs(&f.attrs)?;
+
+                    Ok(FieldInfo {
                         name,
                         ty,
                         is_optional,
                         inner,
-                        each: each_name,
-                    }
+                        each: attrs.each,
+                        set_once: attrs.set_once,
+                        aliases: attrs.aliases,
+                    })
                 })
                 .collect(),
             _ => unimplemented!(),
@@ -97,3 +166,15 @@ pub fn parse_fields(data: &Data) -> Vec<FieldInfo> {
         _ => unimplemented!(),
     }
 }
+
+/// `const fn` setters cannot call `Vec::push`, so `each` setters are rejected
+/// for `#[builder(const)]` builders.
+pub fn check_const(fields: &[FieldInfo]) -> syn::Result<()> {
+    match fields.iter().find_map(|f| f.each.as_ref()) {
+        Option::Some(each) => Err(Error::new_spanned(
+            each,
+            "`each` setters are not supported with `#[builder(const)]`",
+        )),
+        Option::None => Ok(()),
+    }
+}
@@ -1,33 +1,71 @@
 mod builder_struct;
 mod fields;
+mod options;
+mod partial;
 
 use proc_macro::TokenStream;
 
 use quote::{format_ident, quote};
 use syn::{parse_macro_input, DeriveInput};
 use builder_struct::*;
-use fields::parse_fields;
+use fields::{check_const, parse_fields};
+use options::parse_options;
+use partial::impl_partial;
 
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     let name = &input.ident;
-    let fields = parse_fields(&input.data);
+    let options = match parse_options(&input.attrs) {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let fields = match parse_fields(&input.data) {
+        Ok(fields) => fields,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    if options.is_const {
+        if let Err(e) = check_const(&fields) {
+            return e.into_compile_error().into();
+        }
+    }
+
+    let builder_struct_name = options
+        .name
+        .clone()
+        .unwrap_or_else(|| format_ident!("{}Builder", name));
+    let constructor = options
+        .constructor
+        .clone()
+        .unwrap_or_else(|| format_ident!("builder"));
+    // The builder is only as visible as the struct it builds unless overridden.
+    let vis = options.vis.as_ref().unwrap_or(&input.vis);
 
-    let builder_struct_name = format_ident!("{}Builder", name);
-    let builder_struct = get_builder_struct(&fields, &builder_struct_name);
-    let impl_builder = impl_builder(&fields, &builder_struct_name, &name);
-    let init_builder = init_builder_struct(&fields, &builder_struct_name);
+    let builder_struct = get_builder_struct(&fields, &builder_struct_name, vis, &options);
+    let impl_builder = impl_builder(&fields, &builder_struct_name, name, &options);
+    let init_builder = init_builder_struct(&fields, &builder_struct_name, &options);
+    let partial = if options.partial {
+        impl_partial(&fields, name, vis)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    let constness = if options.is_const {
+        quote!(const)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
 
     let expanded = quote! {
         impl #name {
-            pub fn builder() -> #builder_struct_name {
+            #vis #constness fn #constructor() -> #builder_struct_name {
                 #init_builder
             }
         }
         #builder_struct
         #impl_builder
+        #partial
     };
     // For debugging:
     // eprintln!("TOKENS: {}", expanded);
@@ -0,0 +1,111 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Error, Ident, Lit, LitStr, Meta, NestedMeta, Path, Visibility};
+
+type Option<T> = std::option::Option<T>;
+
+/// What a setter does when a `set_once` field is assigned a second time.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum OnDuplicate {
+    #[default]
+    Panic,
+    Error,
+}
+
+/// Struct-level `#[builder(...)]` settings.
+#[derive(Default)]
+pub struct BuilderOptions {
+    pub name: Option<Ident>,
+    pub vis: Option<Visibility>,
+    pub constructor: Option<Ident>,
+    pub derives: Vec<Path>,
+    pub struct_attrs: Vec<TokenStream>,
+    pub set_once: bool,
+    pub on_duplicate: OnDuplicate,
+    pub partial: bool,
+    pub is_const: bool,
+    on_duplicate_span: Option<Span>,
+}
+
+fn lit_str(lit: &Lit) -> syn::Result<&LitStr> {
+    match lit {
+        Lit::Str(s) => Ok(s),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn parse_option(options: &mut BuilderOptions, meta: &Meta) -> syn::Result<()> {
+    let key = match meta.path().get_ident() {
+        Option::Some(ident) => ident.to_string(),
+        Option::None => return Err(Error::new_spanned(meta.path(), "unknown builder option")),
+    };
+    match (key.as_str(), meta) {
+        ("name", Meta::NameValue(nv)) => {
+            options.name = Option::Some(lit_str(&nv.lit)?.parse()?);
+        }
+        ("vis", Meta::NameValue(nv)) => {
+            options.vis = Option::Some(lit_str(&nv.lit)?.parse()?);
+        }
+        ("constructor", Meta::NameValue(nv)) => {
+            options.constructor = Option::Some(lit_str(&nv.lit)?.parse()?);
+        }
+        ("set_once", Meta::Path(_)) => options.set_once = true,
+        ("partial", Meta::Path(_)) => options.partial = true,
+        ("const", Meta::Path(_)) => options.is_const = true,
+        ("on_duplicate", Meta::NameValue(nv)) => {
+            let s = lit_str(&nv.lit)?;
+            options.on_duplicate = match s.value().as_str() {
+                "panic" => OnDuplicate::Panic,
+                "error" => OnDuplicate::Error,
+                _ => return Err(Error::new_spanned(s, r#"expected "panic" or "error""#)),
+            };
+            options.on_duplicate_span = Option::Some(s.span());
+        }
+        ("derive", Meta::List(l)) => {
+            for nested in &l.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(p)) => options.derives.push(p.clone()),
+                    _ => return Err(Error::new_spanned(nested, "expected a trait path")),
+                }
+            }
+        }
+        ("struct_attr", Meta::List(l)) => {
+            for nested in &l.nested {
+                options.struct_attrs.push(quote!(#nested));
+            }
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                meta.path(),
+                format!("unknown builder option `{}`", key),
+            ))
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_options(attrs: &[Attribute]) -> syn::Result<BuilderOptions> {
+    let mut options = BuilderOptions::default();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("builder")) {
+        match attr.parse_meta()? {
+            Meta::List(l) => {
+                for nested in &l.nested {
+                    match nested {
+                        NestedMeta::Meta(m) => parse_option(&mut options, m)?,
+                        NestedMeta::Lit(lit) => {
+                            return Err(Error::new_spanned(lit, "expected a builder option"))
+                        }
+                    }
+                }
+            }
+            m => return Err(Error::new_spanned(m, "expected `builder(...)`")),
+        }
+    }
+    if let (true, OnDuplicate::Error) = (options.is_const, options.on_duplicate) {
+        return Err(Error::new(
+            options.on_duplicate_span.unwrap(),
+            "`#[builder(const)]` builders can only panic on duplicate fields",
+        ));
+    }
+    Ok(options)
+}
@@ -0,0 +1,76 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Ident, Visibility};
+
+use super::fields::FieldInfo;
+
+/// Generates `{Name}Partial`, a copy of the struct in which every field is
+/// optional, for update APIs that only touch some fields of an existing value.
+///
+/// Every field present in the partial replaces the target's value when
+/// applied. `Option` fields become `Option<Option<T>>` so that clearing a
+/// field can be told apart from leaving it alone, and `each` fields carry a
+/// whole collection, so that `diff` followed by `apply_to` always reproduces
+/// the new value.
+pub fn impl_partial(
+    fields: &[FieldInfo],
+    struct_name: &Ident,
+    vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let partial_name = format_ident!("{}Partial", struct_name);
+
+    let partial_fields = fields.iter().map(|f| {
+        let name = f.name;
+        let ty = f.ty;
+        quote_spanned! { name.span()=>
+            pub #name: std::option::Option<#ty>,
+        }
+    });
+
+    let apply = fields.iter().map(|f| {
+        let name = f.name;
+        quote_spanned! { name.span()=>
+            if let std::option::Option::Some(v) = &self.#name {
+                target.#name = std::clone::Clone::clone(v);
+            }
+        }
+    });
+
+    let diff = fields.iter().map(|f| {
+        let name = f.name;
+        quote_spanned! { name.span()=>
+            #name: if old.#name != new.#name {
+                std::option::Option::Some(std::clone::Clone::clone(&new.#name))
+            } else {
+                std::option::Option::None
+            },
+        }
+    });
+
+    let is_empty = fields.iter().map(|f| {
+        let name = f.name;
+        quote! { self.#name.is_none() }
+    });
+
+    quote! {
+        #[derive(Clone, Default)]
+        #vis struct #partial_name {
+            #(#partial_fields)*
+        }
+
+        impl #partial_name {
+            pub fn apply_to(&self, target: &mut #struct_name) {
+                #(#apply)*
+            }
+
+            pub fn diff(old: &#struct_name, new: &#struct_name) -> #partial_name {
+                #partial_name {
+                    #(#diff)*
+                }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                true #(&& #is_empty)*
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// The generated builder struct can be customised from a struct-level
+// `#[builder(...)]` attribute.
+//
+//   - `name = "..."` picks the name of the builder struct,
+//   - `constructor = "..."` renames the `Command::builder()` function,
+//   - `vis = "..."` overrides the visibility, which is otherwise inherited
+//     from the struct being built,
+//   - `derive(...)` forwards derives onto the builder struct,
+//   - `struct_attr(...)` passes arbitrary attributes through to it.
+//
+//     #[derive(Debug, Clone, PartialEq)]
+//     #[allow(dead_code)]
+//     struct CommandFactory {
+//         ...
+//     }
+
+#![deny(private_interfaces)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(name = "CommandFactory", constructor = "factory")]
+#[builder(derive(Debug, Clone, PartialEq), struct_attr(allow(dead_code)))]
+struct Command {
+    executable: String,
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+mod config {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(vis = "pub(crate)")]
+    pub(crate) struct Config {
+        pub(crate) port: u16,
+    }
+}
+
+fn main() {
+    let mut factory: CommandFactory = Command::factory();
+    factory.executable("cargo".to_owned()).args(vec![]);
+
+    let copy = factory.clone();
+    assert_eq!(factory, copy);
+    assert!(format!("{:?}", copy).starts_with("CommandFactory {"));
+
+    let command = factory.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.args.is_empty());
+    assert!(command.current_dir.is_none());
+
+    let config = config::Config::builder().port(80).build().unwrap();
+    assert_eq!(config.port, 80);
+}
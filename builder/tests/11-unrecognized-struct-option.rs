@@ -0,0 +1,12 @@
+// Unknown struct-level options should be reported on the offending key
+// instead of being silently ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(nmae = "CommandFactory")]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {}
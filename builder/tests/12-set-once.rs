@@ -0,0 +1,78 @@
+// Fields marked `#[builder(set_once)]`, or every field when the attribute is
+// placed on the struct, refuse to be assigned a second time.
+//
+// By default the setter panics, and because it is `#[track_caller]` the panic
+// points at the duplicate call. With `#[builder(on_duplicate = "error")]` the
+// setter keeps the first value and the duplicate is reported by `build()`.
+//
+// The per-item setter of an `each` field may still be called repeatedly, and
+// does not count as setting the whole collection.
+
+use derive_builder::Builder;
+use std::panic::{self, Location};
+use std::sync::Mutex;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(set_once)]
+    executable: String,
+    #[builder(set_once, each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(set_once, on_duplicate = "error")]
+pub struct Config {
+    host: String,
+    port: u16,
+}
+
+static PANIC_LINE: Mutex<Option<u32>> = Mutex::new(None);
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .current_dir("..".to_owned())
+        .current_dir("/".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.as_deref(), Some("/"));
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args(vec!["test".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["test"]);
+
+    panic::set_hook(Box::new(|info| {
+        *PANIC_LINE.lock().unwrap() = info.location().map(Location::line);
+    }));
+    let line = Location::caller().line() + 3;
+    let result = panic::catch_unwind(|| {
+        let mut builder = Command::builder();
+        builder.executable("cargo".to_owned()).executable("rustc".to_owned());
+    });
+    let _ = panic::take_hook();
+    let payload = result.unwrap_err();
+    let message = match payload.downcast_ref::<String>() {
+        Some(s) => s.as_str(),
+        None => *payload.downcast_ref::<&str>().unwrap(),
+    };
+    assert_eq!(message, "field `executable` set more than once");
+    assert_eq!(*PANIC_LINE.lock().unwrap(), Some(line));
+
+    let err = Config::builder()
+        .host("localhost".to_owned())
+        .port(80)
+        .port(8080)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "field `port` set more than once");
+}
@@ -0,0 +1,58 @@
+// `#[builder(partial)]` additionally generates `CommandPartial`, a version of
+// the struct in which every field is optional, for PATCH-style updates.
+//
+//     pub struct CommandPartial {
+//         pub executable: Option<String>,
+//         pub args: Option<Vec<String>>,
+//         pub current_dir: Option<Option<String>>,
+//     }
+//
+// Applying a partial replaces exactly the fields it holds, and `diff` builds
+// the partial that turns one value into another.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+#[builder(partial)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+
+    let empty = CommandPartial::default();
+    assert!(empty.is_empty());
+    empty.apply_to(&mut command);
+    assert_eq!(command.executable, "cargo");
+
+    let patch = CommandPartial {
+        args: Some(vec!["test".to_owned()]),
+        current_dir: Some(None),
+        ..CommandPartial::default()
+    };
+    assert!(!patch.is_empty());
+    patch.apply_to(&mut command);
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["test"]);
+    assert_eq!(command.current_dir, None);
+
+    let new = Command {
+        executable: "rustc".to_owned(),
+        ..command.clone()
+    };
+    let diff = CommandPartial::diff(&command, &new);
+    assert_eq!(diff.executable.as_deref(), Some("rustc"));
+    assert!(diff.args.is_none() && diff.current_dir.is_none());
+    diff.apply_to(&mut command);
+    assert_eq!(command, new);
+    assert!(CommandPartial::diff(&command, &new).is_empty());
+}
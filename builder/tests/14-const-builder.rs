@@ -0,0 +1,46 @@
+// `#[builder(const)]` generates `const fn` setters that take and return the
+// builder by value, together with a `const fn build_const()`, so that a value
+// can be assembled in a `static` or `const` item.
+//
+// Replacing a value would require running its destructor during constant
+// evaluation, so every field of a const builder can be set only once.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    host: &'static str,
+    port: u16,
+    name: String,
+    timeout: Option<u64>,
+}
+
+static DEFAULT_CFG: Config = Config::builder()
+    .host("localhost")
+    .port(80)
+    .name(String::new())
+    .build_const();
+
+const WITH_TIMEOUT: Config = Config::builder()
+    .host("example.com")
+    .port(443)
+    .name(String::new())
+    .timeout(30)
+    .build_const();
+
+fn main() {
+    assert_eq!(DEFAULT_CFG.host, "localhost");
+    assert_eq!(DEFAULT_CFG.port, 80);
+    assert!(DEFAULT_CFG.name.is_empty());
+    assert_eq!(DEFAULT_CFG.timeout, None);
+    assert_eq!(WITH_TIMEOUT.timeout, Some(30));
+
+    let config = Config::builder()
+        .host("localhost")
+        .port(8080)
+        .name("runtime".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(config.name, "runtime");
+}
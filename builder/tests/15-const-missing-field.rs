@@ -0,0 +1,15 @@
+// A required field that is never set makes `build_const()` panic, which in a
+// `static` initializer is a compile-time error.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Config {
+    host: &'static str,
+    port: u16,
+}
+
+static DEFAULT_CFG: Config = Config::builder().host("localhost").build_const();
+
+fn main() {}
@@ -0,0 +1,14 @@
+// `each` setters have to push onto a `Vec`, which a `const fn` cannot do, so
+// they are rejected on `#[builder(const)]` builders.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+// When a field is renamed, `#[builder(alias = "...")]` keeps the old setter
+// name working as a deprecated method that forwards to the new setter.
+//
+//     #[deprecated(note = "use `executable` instead")]
+//     pub fn program(&mut self, program: String) -> &mut Self {
+//         self.executable(program)
+//     }
+//
+// The long form `#[builder(alias(name = "...", since = "...", note = "..."))]`
+// controls the attributes of the generated `#[deprecated]`.
+
+#![allow(deprecated)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(alias = "program", alias = "binary")]
+    executable: String,
+    #[builder(each = "arg", alias(name = "argv", since = "2.0", note = "use `args`"))]
+    args: Vec<String>,
+    #[builder(alias(name = "cwd", since = "2.0"))]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .program("cargo".to_owned())
+        .argv(vec!["build".to_owned()])
+        .arg("--release".to_owned())
+        .cwd("..".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    let command = Command::builder()
+        .binary("rustc".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "rustc");
+}
@@ -0,0 +1,21 @@
+// Calls through an alias produce a deprecation warning at the call site,
+// denied here so that it shows up in the expected compiler output.
+
+#![deny(deprecated)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(alias = "program")]
+    executable: String,
+    #[builder(alias(name = "cwd", since = "2.0", note = "renamed to `current_dir`"))]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let _ = Command::builder()
+        .program("cargo".to_owned())
+        .cwd("..".to_owned())
+        .build();
+}
@@ -10,4 +10,13 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-builder-customisation.rs");
+    t.compile_fail("tests/11-unrecognized-struct-option.rs");
+    t.pass("tests/12-set-once.rs");
+    t.pass("tests/13-partial.rs");
+    t.pass("tests/14-const-builder.rs");
+    t.compile_fail("tests/15-const-missing-field.rs");
+    t.compile_fail("tests/16-const-unsupported.rs");
+    t.pass("tests/17-field-alias.rs");
+    t.compile_fail("tests/18-field-alias-deprecated.rs");
 }
@@ -0,0 +1,188 @@
+use std::collections::BTreeSet;
+
+use quote::quote;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_quote, GenericArgument, Generics, Ident, Path, PathArguments, Type, TypePath,
+    WherePredicate,
+};
+
+use super::fields::{Body, Derive, FieldFormatter, FieldInfo, Redact};
+use super::options::DebugOptions;
+use super::template::field_format_traits;
+
+/// Walks a field type, collecting the type parameters it uses directly and the
+/// associated types (`T::Value`) it reaches through them.
+struct BoundCollector<'a> {
+    params: &'a BTreeSet<Ident>,
+    bounded: Vec<TypePath>,
+}
+
+impl<'ast> Visit<'ast> for BoundCollector<'_> {
+    fn visit_type_path(&mut self, tp: &'ast TypePath) {
+        let segments = &tp.path.segments;
+        // `PhantomData<T>` is `Debug` whatever `T` is.
+        if segments.last().is_some_and(|s| s.ident == "PhantomData") {
+            return;
+        }
+        if tp.qself.is_none() && tp.path.leading_colon.is_none() {
+            let first = &segments[0].ident;
+            if self.params.contains(first) {
+                // Bounding `T::Value` directly keeps `T` itself unbounded.
+                let bounded = if segments.len() == 1 {
+                    parse_quote!(#first)
+                } else {
+                    tp.clone()
+                };
+                if !self.bounded.contains(&bounded) {
+                    self.bounded.push(bounded);
+                }
+                return;
+            }
+        }
+        visit::visit_type_path(self, tp);
+    }
+}
+
+/// The type a `sorted` field's items or keys are ordered by: the element of a
+/// slice or array, or else the first type argument, `K` of `HashMap<K, V>`.
+fn sort_key(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(r) => sort_key(&r.elem),
+        Type::Slice(s) => Some(&s.elem),
+        Type::Array(a) => Some(&a.elem),
+        Type::Path(tp) => match &tp.path.segments.last()?.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Traits the generated code needs of a field's type.
+fn field_traits(f: &FieldInfo) -> Vec<Path> {
+    if f.flatten {
+        return vec![parse_quote!(::derive_debug::DebugFields)];
+    }
+    match (&f.redact, &f.formatter) {
+        (Some(Redact::Partial), _) => {
+            vec![
+                parse_quote!(::core::fmt::Debug),
+                parse_quote!(::core::fmt::Display),
+            ]
+        }
+        (Some(_), _) => vec![parse_quote!(::core::fmt::Debug)],
+        // The user's function, or the runtime wrapper's impls, decide what is
+        // required.
+        (None, Some(FieldFormatter::With(_) | FieldFormatter::Pointer | FieldFormatter::CStr)) => {
+            Vec::new()
+        }
+        (None, Some(FieldFormatter::Hex | FieldFormatter::Binary)) => {
+            vec![parse_quote!(::derive_debug::Radix)]
+        }
+        (None, Some(FieldFormatter::Display)) => vec![parse_quote!(::core::fmt::Display)],
+        // A format string selects a trait per placeholder, like a template.
+        // Without one, `alt_format` falls back to `Debug` outside `{:#?}`.
+        (None, None) => {
+            let mut traits = match &f.format {
+                Some(format) => field_format_traits(format),
+                None => vec![parse_quote!(::core::fmt::Debug)],
+            };
+            if let Some(alt_format) = &f.alt_format {
+                traits.extend(field_format_traits(alt_format));
+            }
+            traits
+        }
+    }
+}
+
+/// Adds a `Debug` bound, or whichever trait formats the field, for every type
+/// parameter, or associated type of one, that shows up in a formatted field.
+///
+/// Bounds are placed on the parameters rather than the field types so that
+/// recursive types such as `One<T>` containing `Two<T>` do not end up with
+/// bounds that require their own impl.
+///
+/// A struct-level `#[debug(bound = "...")]` replaces inference altogether,
+/// while a field-level one replaces only what that field would contribute.
+pub fn add_trait_bounds(
+    generics: &Generics,
+    body: &Body,
+    options: &DebugOptions,
+    derive: Derive,
+) -> Generics {
+    let mut bounded = generics.clone();
+    if let Some(bound) = &options.bound {
+        bounded
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+        return bounded;
+    }
+
+    let params = generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<BTreeSet<_>>();
+    let mut predicates = Vec::<WherePredicate>::new();
+    for variant in body.variants() {
+        for (i, f) in variant.fields.iter().enumerate() {
+            // A template formats only the fields it names, through the traits
+            // their format specs select. Otherwise skipped fields are never
+            // formatted and need no bound.
+            let traits = match &variant.template {
+                Some(template) => template
+                    .args
+                    .iter()
+                    .filter(|(index, _)| *index == i)
+                    .map(|(_, tr)| tr.clone())
+                    .collect(),
+                None if variant.transparent && derive == Derive::Display => {
+                    vec![derive.trait_path()]
+                }
+                None if f.skip => continue,
+                None => field_traits(f),
+            };
+            if traits.is_empty() {
+                continue;
+            }
+            if let Some(bound) = &f.bound {
+                predicates.extend(bound.iter().cloned());
+                continue;
+            }
+            let mut collector = BoundCollector {
+                params: &params,
+                bounded: Vec::new(),
+            };
+            collector.visit_type(f.ty);
+            for ty in &collector.bounded {
+                for tr in &traits {
+                    predicates.push(parse_quote!(#ty: #tr));
+                }
+            }
+            // Sorting also needs the items, or the keys of a map, to be `Ord`.
+            if let (true, None, Some(key)) = (f.sorted, &variant.template, sort_key(f.ty)) {
+                let mut collector = BoundCollector {
+                    params: &params,
+                    bounded: Vec::new(),
+                };
+                collector.visit_type(key);
+                for ty in &collector.bounded {
+                    predicates.push(parse_quote!(#ty: ::core::cmp::Ord));
+                }
+            }
+        }
+    }
+
+    let where_clause = bounded.make_where_clause();
+    let mut seen = BTreeSet::new();
+    for predicate in predicates {
+        if seen.insert(quote!(#predicate).to_string()) {
+            where_clause.predicates.push(predicate);
+        }
+    }
+    bounded
+}
@@ -0,0 +1,120 @@
+use quote::{format_ident, quote};
+use syn::{Generics, Ident};
+
+use super::fields::{Body, FieldInfo, VariantInfo};
+use super::{field_value_of, variant_pattern, variant_pattern_as};
+
+/// Local a field of `other` is bound to, alongside `self`'s `__self_{i}`.
+fn other_binding(f: &FieldInfo) -> Ident {
+    let binding = f.binding.to_string();
+    format_ident!("__other_{}", &binding["__self_".len()..])
+}
+
+/// Statement comparing one field. Formatting attributes wrap both values the
+/// same way, and wrapped values are compared by their output.
+fn diff_field(f: &FieldInfo, index: usize) -> proc_macro2::TokenStream {
+    let left = field_value_of(f, &f.binding);
+    let right = field_value_of(f, &other_binding(f));
+    // A flattened struct's fields appear as the parent's own.
+    if f.flatten {
+        return quote! {
+            (&::derive_debug::__private::DiffFlattened(#left, #right)).diff_into(__path, __out);
+        };
+    }
+    let segment = match (&f.rename, f.name) {
+        (Some(rename), _) => rename.value(),
+        (None, Some(name)) => name.to_string(),
+        (None, None) => index.to_string(),
+    };
+    quote! {
+        (&::derive_debug::__private::DiffField(#left, #right))
+            .diff_into(&::derive_debug::__private::join_path(__path, #segment), __out);
+    }
+}
+
+/// Match arm comparing two values of the same variant.
+fn diff_arm(variant: &VariantInfo, path: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let compared =
+        |_: usize, f: &FieldInfo| variant.template.is_none() && (variant.transparent || !f.skip);
+    let left = variant_pattern(variant, path.clone(), compared);
+    let right = variant_pattern_as(variant, path, compared, other_binding);
+    let body = if variant.template.is_some() {
+        // Nothing but the whole output is known to be meaningful.
+        quote!(::derive_debug::__private::diff_debug(__path, self, __other, __out);)
+    } else if variant.transparent {
+        // The only field stands for the value itself.
+        let f = &variant.fields[0];
+        let (left, right) = (
+            field_value_of(f, &f.binding),
+            field_value_of(f, &other_binding(f)),
+        );
+        quote!((&::derive_debug::__private::DiffField(#left, #right)).diff_into(__path, __out);)
+    } else {
+        let fields = variant
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.skip)
+            .map(|(i, f)| diff_field(f, i));
+        quote!(#(#fields)*)
+    };
+    quote! {
+        (#left, #right) => {
+            #body
+        }
+    }
+}
+
+pub fn impl_debug_diff(body: &Body, name: &Ident, generics: &Generics) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let diff_body = match body {
+        Body::Struct(variant) => {
+            let arm = diff_arm(variant, quote!(Self));
+            quote! {
+                match (self, __other) {
+                    #arm
+                }
+            }
+        }
+        // Which field of a union is valid is not known, so the values are
+        // compared as a whole through Debug.
+        Body::Union(_) => quote!(::derive_debug::__private::diff_debug(
+            __path, self, __other, __out
+        )),
+        // An empty enum has no values to compare.
+        Body::Enum(variants) if variants.is_empty() => quote!(match *self {}),
+        Body::Enum(variants) => {
+            let arms = variants.iter().map(|v| {
+                let ident = v.ident;
+                diff_arm(v, quote!(Self::#ident))
+            });
+            // Different variants differ as a whole.
+            let fallback = if variants.len() > 1 {
+                quote!(_ => ::derive_debug::__private::diff_debug(__path, self, __other, __out),)
+            } else {
+                quote!()
+            };
+            quote! {
+                match (self, __other) {
+                    #(#arms)*
+                    #fallback
+                }
+            }
+        }
+    };
+    quote! {
+        impl #impl_generics ::derive_debug::DebugDiff for #name #ty_generics #where_clause {
+            fn debug_diff_into(
+                &self,
+                __other: &Self,
+                __path: &::core::primitive::str,
+                __out: &mut ::derive_debug::__private::Vec<::derive_debug::FieldDiff>,
+            ) {
+                #[allow(unused_imports)]
+                use ::derive_debug::__private::{DiffDebug as _, DiffNested as _};
+                #diff_body
+            }
+        }
+    }
+}
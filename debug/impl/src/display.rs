@@ -0,0 +1,78 @@
+use quote::quote;
+use syn::{Error, Generics, Ident};
+
+use super::fields::{Body, Style, VariantInfo};
+use super::{template_arm, variant_pattern};
+
+/// Pattern and formatting code for one struct or enum variant, which must
+/// have a template or be `transparent` unless it is a unit variant.
+fn display_arm(
+    variant: &VariantInfo,
+    path: proc_macro2::TokenStream,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    if let Some(template) = &variant.template {
+        return Ok(template_arm(variant, template, path));
+    }
+    if variant.transparent {
+        let binding = &variant.fields[0].binding;
+        let pattern = variant_pattern(variant, path, |_, _| true);
+        return Ok((pattern, quote!(::core::fmt::Display::fmt(#binding, f))));
+    }
+    match variant.style {
+        Style::Unit => {
+            let name = &variant.name;
+            Ok((quote!(#path), quote!(f.write_str(#name))))
+        }
+        _ => Err(Error::new_spanned(
+            variant.ident,
+            r#"CustomDisplay needs `#[display("...")]` or `#[display(transparent)]`"#,
+        )),
+    }
+}
+
+pub fn impl_display(
+    body: &Body,
+    name: &Ident,
+    generics: &Generics,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fmt_body = match body {
+        Body::Struct(variant) => {
+            let (pattern, fmt_variant) = display_arm(variant, quote!(Self))?;
+            quote! {
+                let #pattern = self;
+                #fmt_variant
+            }
+        }
+        Body::Union(_) => unreachable!("CustomDisplay rejects unions while parsing"),
+        // An empty enum has no values to format.
+        Body::Enum(variants) if variants.is_empty() => quote!(match *self {}),
+        Body::Enum(variants) => {
+            let arms = variants
+                .iter()
+                .map(|v| {
+                    let ident = v.ident;
+                    let (pattern, fmt_variant) = display_arm(v, quote!(Self::#ident))?;
+                    Ok(quote! {
+                        #pattern => {
+                            #fmt_variant
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #fmt_body
+            }
+        }
+    })
+}
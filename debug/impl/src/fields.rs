@@ -0,0 +1,821 @@
+use quote::{format_ident, ToTokens};
+use syn::{
+    parse_quote, punctuated::Punctuated, Attribute, Data, Error, Field, Fields, Ident, Lit, LitStr,
+    Meta, NestedMeta, Path, Token, Type, WherePredicate,
+};
+
+use super::options::{DebugOptions, Extra};
+use super::template::{check_field_format, parse_template, Template};
+
+type Option<T> = std::option::Option<T>;
+
+/// The derive whose helper attributes are being parsed. Both read the same
+/// keys, from `#[debug(...)]` and `#[display(...)]` respectively.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Derive {
+    Debug,
+    Display,
+}
+
+impl Derive {
+    pub fn attr(self) -> &'static str {
+        match self {
+            Derive::Debug => "debug",
+            Derive::Display => "display",
+        }
+    }
+
+    pub fn macro_name(self) -> &'static str {
+        match self {
+            Derive::Debug => "CustomDebug",
+            Derive::Display => "CustomDisplay",
+        }
+    }
+
+    /// The trait the derive implements.
+    pub fn trait_path(self) -> Path {
+        match self {
+            Derive::Debug => parse_quote!(::core::fmt::Debug),
+            Derive::Display => parse_quote!(::core::fmt::Display),
+        }
+    }
+}
+
+pub struct FieldInfo<'a> {
+    pub name: &'a Option<Ident>,
+    pub ty: &'a Type,
+    /// Local the field is bound to when `self` is destructured.
+    pub binding: Ident,
+    /// `#[debug(rename = "...")]`, the label printed instead of the name.
+    pub rename: Option<LitStr>,
+    pub format: Option<LitStr>,
+    /// `#[debug(alt_format = "...")]`, used instead of `format` in `{:#?}`.
+    pub alt_format: Option<LitStr>,
+    pub bound: Option<Vec<WherePredicate>>,
+    pub skip: bool,
+    pub skip_if: Option<Path>,
+    pub redact: Option<Redact>,
+    pub formatter: Option<FieldFormatter>,
+    pub collection: Option<Collection>,
+    pub max_items: Option<usize>,
+    pub max_len: Option<usize>,
+    pub sorted: bool,
+    /// `#[debug(flatten)]`, listing the inner struct's fields in place.
+    pub flatten: bool,
+}
+
+/// Standard container a field's type is recognised as, going by the last
+/// segment of its path.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Collection {
+    List,
+    Set,
+    Map,
+    Str,
+}
+
+pub fn collection(ty: &Type) -> Option<Collection> {
+    match ty {
+        Type::Reference(r) => collection(&r.elem),
+        Type::Slice(_) | Type::Array(_) => Option::Some(Collection::List),
+        Type::Path(tp) => match tp.path.segments.last()?.ident.to_string().as_str() {
+            "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" => Option::Some(Collection::List),
+            "HashSet" | "BTreeSet" => Option::Some(Collection::Set),
+            "HashMap" | "BTreeMap" => Option::Some(Collection::Map),
+            "String" | "str" => Option::Some(Collection::Str),
+            _ => Option::None,
+        },
+        _ => Option::None,
+    }
+}
+
+/// Whether a field is a `HashMap` or `HashSet`, whose iteration order is
+/// unspecified.
+fn is_hash_collection(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => is_hash_collection(&r.elem),
+        Type::Path(tp) => tp
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "HashMap" || s.ident == "HashSet"),
+        _ => false,
+    }
+}
+
+/// A replacement for the field type's own `Debug` impl.
+pub enum FieldFormatter {
+    /// `#[debug(with = "path")]`, a `fn(&T, &mut fmt::Formatter) -> fmt::Result`.
+    With(Path),
+    Display,
+    Hex,
+    Binary,
+    /// A raw pointer, printed as its address or as `null`.
+    Pointer,
+    /// `#[debug(cstr)]`, a byte buffer printed as the C string it holds.
+    CStr,
+}
+
+impl FieldFormatter {
+    /// The attribute key selecting the formatter.
+    fn key(&self) -> &'static str {
+        match self {
+            FieldFormatter::With(_) => "with",
+            FieldFormatter::Display => "display",
+            FieldFormatter::Hex => "hex",
+            FieldFormatter::Binary => "binary",
+            FieldFormatter::Pointer => "pointer",
+            FieldFormatter::CStr => "cstr",
+        }
+    }
+}
+
+/// How a `#[debug(redact)]` field hides its value.
+pub enum Redact {
+    Full,
+    Partial,
+    Hash,
+}
+
+pub enum Style {
+    Named,
+    Tuple,
+    Unit,
+}
+
+/// The fields of a struct or of one enum variant.
+pub struct VariantInfo<'a> {
+    pub ident: &'a Ident,
+    /// Name printed for the struct or variant, which `#[debug(name = "...")]`
+    /// overrides for a struct.
+    pub name: String,
+    pub style: Style,
+    pub fields: Vec<FieldInfo<'a>>,
+    /// `#[debug("...")]` replacing the derived output altogether.
+    pub template: Option<Template>,
+    /// `#[debug(transparent)]`, forwarding to the only field.
+    pub transparent: bool,
+    /// Computed entries following the fields of a struct.
+    pub extra: Vec<Extra>,
+}
+
+/// A template or `transparent`, given on a struct or on an enum variant.
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub template: Option<LitStr>,
+    pub transparent: Option<Path>,
+}
+
+/// Parses `nested` into `attrs` if it is a template or `transparent`, and
+/// returns whether it was.
+pub fn parse_variant_attr(attrs: &mut VariantAttrs, nested: &NestedMeta) -> bool {
+    match nested {
+        NestedMeta::Lit(Lit::Str(s)) => attrs.template = Option::Some(s.clone()),
+        NestedMeta::Meta(Meta::Path(p)) if p.is_ident("transparent") => {
+            attrs.transparent = Option::Some(p.clone());
+        }
+        _ => return false,
+    }
+    true
+}
+
+pub enum Body<'a> {
+    Struct(VariantInfo<'a>),
+    Enum(Vec<VariantInfo<'a>>),
+    /// A union, whose fields are all skipped except the one chosen by
+    /// `#[debug(union_field = "...")]`.
+    Union(VariantInfo<'a>),
+}
+
+impl<'a> Body<'a> {
+    pub fn variants(&self) -> &[VariantInfo<'a>] {
+        match self {
+            Body::Struct(v) | Body::Union(v) => std::slice::from_ref(v),
+            Body::Enum(variants) => variants,
+        }
+    }
+}
+
+pub fn parse_body<'a>(
+    ident: &'a Ident,
+    data: &'a Data,
+    options: &DebugOptions,
+    derive: Derive,
+) -> syn::Result<Body<'a>> {
+    if let (Option::Some(union_field), false) =
+        (&options.union_field, matches!(data, Data::Union(_)))
+    {
+        return Err(Error::new_spanned(
+            union_field,
+            "`union_field` applies to unions",
+        ));
+    }
+    match data {
+        Data::Struct(data) => {
+            let mut variant = parse_variant(
+                ident,
+                style(&data.fields),
+                data.fields.iter(),
+                &options.variant,
+                options,
+                derive,
+            )?;
+            if let Option::Some(name) = &options.name {
+                variant.name = name.value();
+            }
+            if let Option::Some(extra) = options.extra.first() {
+                if !matches!(variant.style, Style::Named)
+                    || variant.template.is_some()
+                    || variant.transparent
+                {
+                    return Err(Error::new_spanned(
+                        &extra.name,
+                        "`extra` needs a struct with named fields and no template or `transparent`",
+                    ));
+                }
+                variant.extra = options.extra.clone();
+            }
+            Ok(Body::Struct(variant))
+        }
+        Data::Enum(data) => {
+            if let Option::Some(name) = &options.name {
+                return Err(Error::new_spanned(
+                    name,
+                    "`name` applies to structs; enum variants print their own names",
+                ));
+            }
+            if let Option::Some(extra) = options.extra.first() {
+                return Err(Error::new_spanned(
+                    &extra.name,
+                    "`extra` applies to structs",
+                ));
+            }
+            if let Option::Some(template) = &options.variant.template {
+                return Err(Error::new_spanned(
+                    template,
+                    "a template on an enum must be given on each variant",
+                ));
+            }
+            if let Option::Some(transparent) = &options.variant.transparent {
+                return Err(Error::new_spanned(
+                    transparent,
+                    "`transparent` on an enum must be given on each variant",
+                ));
+            }
+            Ok(Body::Enum(
+                data.variants
+                    .iter()
+                    .map(|v| {
+                        let attrs = parse_variant_attrs(&v.attrs, derive)?;
+                        parse_variant(
+                            &v.ident,
+                            style(&v.fields),
+                            v.fields.iter(),
+                            &attrs,
+                            options,
+                            derive,
+                        )
+                    })
+                    .collect::<syn::Result<_>>()?,
+            ))
+        }
+        Data::Union(data) if derive == Derive::Display => Err(Error::new_spanned(
+            data.union_token,
+            format!("{} does not support unions", derive.macro_name()),
+        )),
+        Data::Union(data) => {
+            if let Option::Some(extra) = options.extra.first() {
+                return Err(Error::new_spanned(
+                    &extra.name,
+                    "`extra` applies to structs",
+                ));
+            }
+            // Both would read fields whatever the union currently holds.
+            if let Option::Some(template) = &options.variant.template {
+                return Err(Error::new_spanned(
+                    template,
+                    "a template cannot be used on a union",
+                ));
+            }
+            if let Option::Some(transparent) = &options.variant.transparent {
+                return Err(Error::new_spanned(
+                    transparent,
+                    "`transparent` cannot be used on a union",
+                ));
+            }
+            let mut variant = parse_variant(
+                ident,
+                Style::Named,
+                data.fields.named.iter(),
+                &options.variant,
+                options,
+                derive,
+            )?;
+            if let Option::Some(name) = &options.name {
+                variant.name = name.value();
+            }
+            let chosen = options.union_field.as_ref().map(LitStr::value);
+            let is_chosen = |f: &FieldInfo| f.name.as_ref().map(Ident::to_string) == chosen;
+            if let Option::Some(union_field) = &options.union_field {
+                match variant.fields.iter().find(|f| is_chosen(f)) {
+                    Option::None => {
+                        return Err(Error::new_spanned(
+                            union_field,
+                            format!("union `{}` has no field `{}`", ident, union_field.value()),
+                        ));
+                    }
+                    Option::Some(f) if f.skip => {
+                        return Err(Error::new_spanned(
+                            union_field,
+                            format!(
+                                "`union_field` names `{}`, which is marked `skip`",
+                                union_field.value()
+                            ),
+                        ));
+                    }
+                    Option::Some(_) => {}
+                }
+            }
+            for f in &mut variant.fields {
+                f.skip = !is_chosen(f);
+            }
+            Ok(Body::Union(variant))
+        }
+    }
+}
+
+/// Parses the template or `transparent` of an enum variant.
+fn parse_variant_attrs(attrs: &[Attribute], derive: Derive) -> syn::Result<VariantAttrs> {
+    let mut variant = VariantAttrs::default();
+    for attr in helper_attrs(attrs, derive) {
+        match attr.parse_meta()? {
+            Meta::List(l) => {
+                for nested in l.nested {
+                    if !parse_variant_attr(&mut variant, &nested) {
+                        return Err(unknown_key(
+                            &nested,
+                            &format!("{} attribute", derive.attr()),
+                            VARIANT_KEYS,
+                        ));
+                    }
+                }
+            }
+            m => {
+                return Err(Error::new_spanned(
+                    m,
+                    format!(r#"expected `{}("...")`"#, derive.attr()),
+                ))
+            }
+        }
+    }
+    Ok(variant)
+}
+
+fn style(fields: &Fields) -> Style {
+    match fields {
+        Fields::Named(_) => Style::Named,
+        Fields::Unnamed(_) => Style::Tuple,
+        Fields::Unit => Style::Unit,
+    }
+}
+
+fn parse_variant<'a>(
+    ident: &'a Ident,
+    style: Style,
+    fields: impl Iterator<Item = &'a Field>,
+    attrs: &VariantAttrs,
+    options: &DebugOptions,
+    derive: Derive,
+) -> syn::Result<VariantInfo<'a>> {
+    let fields = fields
+        .enumerate()
+        .map(|(i, f)| {
+            let mut info = FieldInfo {
+                name: &f.ident,
+                ty: &f.ty,
+                binding: format_ident!("__self_{}", i),
+                rename: Option::None,
+                format: Option::None,
+                alt_format: Option::None,
+                bound: Option::None,
+                skip: false,
+                skip_if: Option::None,
+                redact: Option::None,
+                formatter: Option::None,
+                collection: collection(&f.ty),
+                max_items: Option::None,
+                max_len: Option::None,
+                sorted: false,
+                flatten: false,
+            };
+            for attr in helper_attrs(&f.attrs, derive) {
+                match_meta(&mut info, attr.parse_meta()?, derive)?;
+            }
+            if let (Option::Some(rename), Option::None) = (&info.rename, info.name) {
+                return Err(Error::new_spanned(
+                    rename,
+                    "`rename` applies only to named fields",
+                ));
+            }
+            // A template formats the fields it uses itself, so the defaults
+            // below would have no effect.
+            if attrs.template.is_some() {
+                return Ok(info);
+            }
+            // Fields printed some other way are left unsorted.
+            info.sorted |= options.sorted_collections
+                && is_hash_collection(&f.ty)
+                && info.redact.is_none()
+                && info.formatter.is_none()
+                && info.format.is_none()
+                && info.alt_format.is_none();
+            // Printing a raw pointer never reads what it points to.
+            if let (Type::Ptr(_), Option::None, Option::None, Option::None) =
+                (&f.ty, &info.formatter, &info.format, &info.alt_format)
+            {
+                info.formatter = Option::Some(FieldFormatter::Pointer);
+            }
+            // Struct-level limits apply only to fields recognisably of a type
+            // they make sense for.
+            match info.collection {
+                Option::Some(Collection::Str) => {
+                    info.max_len = info.max_len.or(options.max_len);
+                }
+                Option::Some(_) => info.max_items = info.max_items.or(options.max_items),
+                Option::None => {}
+            }
+            Ok(info)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let template = match &attrs.template {
+        Option::Some(lit) => Option::Some(parse_template(lit, &ident.to_string(), &fields)?),
+        Option::None => Option::None,
+    };
+    if let Option::Some(transparent) = &attrs.transparent {
+        if template.is_some() {
+            return Err(Error::new_spanned(
+                transparent,
+                "`transparent` cannot be combined with a template",
+            ));
+        }
+        if fields.len() != 1 {
+            return Err(Error::new_spanned(
+                transparent,
+                "`transparent` requires exactly one field",
+            ));
+        }
+        // The field is always forwarded to, so it cannot be left out.
+        if fields[0].skip || fields[0].skip_if.is_some() {
+            return Err(Error::new_spanned(
+                transparent,
+                "`transparent` cannot forward to a skipped field",
+            ));
+        }
+    }
+    Ok(VariantInfo {
+        ident,
+        name: ident.to_string(),
+        style,
+        fields,
+        template,
+        transparent: attrs.transparent.is_some(),
+        extra: Vec::new(),
+    })
+}
+
+/// The `#[debug(...)]` or `#[display(...)]` attributes among `attrs`.
+pub fn helper_attrs(attrs: &[Attribute], derive: Derive) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(move |a| a.path.is_ident(derive.attr()))
+}
+
+pub fn lit_str(lit: &Lit) -> syn::Result<&LitStr> {
+    match lit {
+        Lit::Str(s) => Ok(s),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+pub fn lit_usize(lit: &Lit) -> syn::Result<usize> {
+    match lit {
+        Lit::Int(i) => i.base10_parse(),
+        _ => Err(Error::new_spanned(lit, "expected an integer literal")),
+    }
+}
+
+/// Keys of `#[debug(...)]` on a field.
+const FIELD_KEYS: &[&str] = &[
+    "alt_format",
+    "binary",
+    "bound",
+    "cstr",
+    "display",
+    "flatten",
+    "format",
+    "hex",
+    "max_items",
+    "max_len",
+    "redact",
+    "rename",
+    "skip",
+    "skip_if",
+    "sorted",
+    "with",
+];
+
+/// Keys of `#[display(...)]` on a field. A template or `transparent` does all
+/// the formatting, so only bounds apply.
+const DISPLAY_FIELD_KEYS: &[&str] = &["bound"];
+
+/// Keys of `#[debug(...)]` on an enum variant.
+const VARIANT_KEYS: &[&str] = &["transparent"];
+
+/// Rejects a key among `known`, those `#[debug(...)]` accepts, that `derive`
+/// does not support.
+pub fn check_supported(
+    nested: &NestedMeta,
+    derive: Derive,
+    known: &[&str],
+    supported: &[&str],
+) -> syn::Result<()> {
+    let key = match nested {
+        NestedMeta::Meta(m) => m.path().get_ident(),
+        NestedMeta::Lit(_) => Option::None,
+    };
+    match key {
+        Option::Some(key)
+            if known.contains(&key.to_string().as_str())
+                && !supported.contains(&key.to_string().as_str()) =>
+        {
+            Err(Error::new_spanned(
+                key,
+                format!("`{}` is not supported by {}", key, derive.macro_name()),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Error for a key that is not among `known`, or that is but was given in the
+/// wrong form, suggesting the closest known key for a likely typo.
+pub fn unknown_key(nested: &NestedMeta, what: &str, known: &[&str]) -> Error {
+    let path = match nested {
+        NestedMeta::Meta(m) => m.path(),
+        NestedMeta::Lit(lit) => {
+            return Error::new_spanned(lit, format!("unexpected literal in {}", what))
+        }
+    };
+    let key = quote::quote!(#path).to_string().replace(' ', "");
+    if known.contains(&key.as_str()) {
+        return Error::new_spanned(nested, format!("malformed {} `{}`", what, key));
+    }
+    let mut message = format!("unknown {} `{}`", what, key);
+    let closest = known
+        .iter()
+        .map(|k| (edit_distance(&key, k), k))
+        .filter(|(distance, k)| *distance <= (k.len() / 3).max(1))
+        .min();
+    if let Option::Some((_, k)) = closest {
+        message.push_str(&format!("; did you mean `{}`?", k));
+    }
+    Error::new_spanned(path, message)
+}
+
+/// Levenshtein distance, counting a swap of two adjacent characters as one
+/// edit since that is the most common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Parses the predicates of a `bound = "..."` attribute.
+pub fn parse_bound(lit: &Lit) -> syn::Result<Vec<WherePredicate>> {
+    let predicates =
+        lit_str(lit)?.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// Keys of a field attribute that decide how the field's value is printed.
+const FORMATTING_KEYS: &[&str] = &[
+    "alt_format",
+    "binary",
+    "cstr",
+    "display",
+    "format",
+    "hex",
+    "max_items",
+    "max_len",
+    "redact",
+    "sorted",
+    "with",
+];
+
+/// Formatting keys that apply together. Any other two would have one
+/// overriding or ignoring the other.
+const COMBINABLE_KEYS: &[(&str, &str)] = &[("alt_format", "format"), ("max_items", "sorted")];
+
+/// The formatting keys given on a field so far.
+fn formatting_keys(info: &FieldInfo) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    if let Option::Some(formatter) = &info.formatter {
+        keys.push(formatter.key());
+    }
+    if info.format.is_some() {
+        keys.push("format");
+    }
+    if info.alt_format.is_some() {
+        keys.push("alt_format");
+    }
+    if info.redact.is_some() {
+        keys.push("redact");
+    }
+    if info.sorted {
+        keys.push("sorted");
+    }
+    if info.max_items.is_some() {
+        keys.push("max_items");
+    }
+    if info.max_len.is_some() {
+        keys.push("max_len");
+    }
+    keys
+}
+
+/// A key given on `f` that a template using the field would ignore.
+pub fn template_ignored_key(f: &FieldInfo) -> Option<&'static str> {
+    let mut keys = formatting_keys(f);
+    for (key, given) in [
+        ("flatten", f.flatten),
+        ("rename", f.rename.is_some()),
+        ("skip", f.skip),
+        ("skip_if", f.skip_if.is_some()),
+    ] {
+        if given {
+            keys.push(key);
+        }
+    }
+    keys.first().copied()
+}
+
+/// Rejects formatting key `key`, spanned by `tokens`, on a field already
+/// given a formatting key it cannot be combined with. `flatten` replaces the
+/// field's entry with the inner struct's, so it combines with none of them,
+/// nor with `rename`.
+fn check_conflict(info: &FieldInfo, key: &str, tokens: &dyn ToTokens) -> syn::Result<()> {
+    let conflict = if key == "flatten" {
+        let rename = info.rename.as_ref().map(|_| "rename");
+        formatting_keys(info).into_iter().chain(rename).next()
+    } else if info.flatten && (key == "rename" || FORMATTING_KEYS.contains(&key)) {
+        Option::Some("flatten")
+    } else if FORMATTING_KEYS.contains(&key) {
+        formatting_keys(info).into_iter().find(|&given| {
+            given != key
+                && !COMBINABLE_KEYS.contains(&(given, key))
+                && !COMBINABLE_KEYS.contains(&(key, given))
+        })
+    } else {
+        Option::None
+    };
+    match conflict {
+        Option::Some(given) => Err(Error::new_spanned(
+            tokens,
+            format!("`{}` cannot be combined with `{}`", key, given),
+        )),
+        Option::None => Ok(()),
+    }
+}
+
+fn match_meta(info: &mut FieldInfo, m: Meta, derive: Derive) -> syn::Result<()> {
+    match m {
+        Meta::List(l) => {
+            for nested in l.nested {
+                if derive == Derive::Display {
+                    check_supported(&nested, derive, FIELD_KEYS, DISPLAY_FIELD_KEYS)?;
+                }
+                if let NestedMeta::Meta(m) = &nested {
+                    if let Option::Some(key) = m.path().get_ident() {
+                        check_conflict(info, &key.to_string(), key)?;
+                    }
+                }
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("bound") => {
+                        info.bound = Option::Some(parse_bound(&nv.lit)?);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("format") => {
+                        let format = lit_str(&nv.lit)?;
+                        check_field_format(format)?;
+                        info.format = Option::Some(format.clone());
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("alt_format") => {
+                        let format = lit_str(&nv.lit)?;
+                        check_field_format(format)?;
+                        info.alt_format = Option::Some(format.clone());
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                        info.rename = Option::Some(lit_str(&nv.lit)?.clone());
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip") => info.skip = true,
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("skip_if") => {
+                        info.skip_if = Option::Some(lit_str(&nv.lit)?.parse()?);
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("redact") => {
+                        info.redact = Option::Some(Redact::Full);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("redact") => {
+                        let mode = lit_str(&nv.lit)?;
+                        info.redact = Option::Some(match mode.value().as_str() {
+                            "full" => Redact::Full,
+                            "partial" => Redact::Partial,
+                            "hash" => Redact::Hash,
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    mode,
+                                    r#"expected "full", "partial" or "hash""#,
+                                ))
+                            }
+                        });
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("with") => {
+                        let path = lit_str(&nv.lit)?.parse()?;
+                        info.formatter = Option::Some(FieldFormatter::With(path));
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("display") => {
+                        info.formatter = Option::Some(FieldFormatter::Display);
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("hex") => {
+                        info.formatter = Option::Some(FieldFormatter::Hex);
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("binary") => {
+                        info.formatter = Option::Some(FieldFormatter::Binary);
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("cstr") => {
+                        info.formatter = Option::Some(FieldFormatter::CStr);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_items") => {
+                        info.max_items = Option::Some(lit_usize(&nv.lit)?);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_len") => {
+                        info.max_len = Option::Some(lit_usize(&nv.lit)?);
+                    }
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("sorted") => info.sorted = true,
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("flatten") => {
+                        if info.name.is_none() {
+                            return Err(Error::new_spanned(
+                                p,
+                                "`flatten` applies only to named fields",
+                            ));
+                        }
+                        info.flatten = true;
+                    }
+                    nested => {
+                        return Err(unknown_key(
+                            &nested,
+                            &format!("{} attribute", derive.attr()),
+                            match derive {
+                                Derive::Debug => FIELD_KEYS,
+                                Derive::Display => DISPLAY_FIELD_KEYS,
+                            },
+                        ))
+                    }
+                }
+            }
+        }
+        Meta::NameValue(nv) if derive == Derive::Display => {
+            return Err(Error::new_spanned(
+                nv,
+                "a field format is not supported by CustomDisplay",
+            ))
+        }
+        Meta::NameValue(nv) => {
+            check_conflict(info, "format", &nv)?;
+            let format = lit_str(&nv.lit)?;
+            check_field_format(format)?;
+            info.format = Option::Some(format.clone());
+        }
+        Meta::Path(p) => {
+            return Err(Error::new_spanned(
+                p,
+                format!(r#"expected `{} = "..."`"#, derive.attr()),
+            ))
+        }
+    }
+    Ok(())
+}
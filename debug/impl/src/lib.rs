@@ -0,0 +1,499 @@
+mod bound;
+mod diff;
+mod display;
+mod fields;
+mod options;
+mod template;
+
+use proc_macro::TokenStream;
+
+use bound::add_trait_bounds;
+use fields::*;
+use options::parse_options;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Generics, Ident, LitStr};
+
+use diff::impl_debug_diff;
+use display::impl_display;
+use template::Template;
+
+#[proc_macro_derive(CustomDebug, attributes(debug))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+
+    let options = match parse_options(&input.attrs, Derive::Debug) {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let body = match parse_body(name, &input.data, &options, Derive::Debug) {
+        Ok(body) => body,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let generics = add_trait_bounds(&input.generics, &body, &options, Derive::Debug);
+
+    let debug_impl = impl_debug(&body, name, &generics, options.max_depth);
+    let debug_fields_impl = impl_debug_fields(&body, name, &generics);
+    let tokens = quote! {
+        #debug_impl
+        #debug_fields_impl
+    };
+    // eprintln!("Tokens: {}", tokens);
+    tokens.into()
+}
+
+#[proc_macro_derive(DebugDiff, attributes(debug))]
+pub fn derive_debug_diff(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+
+    let options = match parse_options(&input.attrs, Derive::Debug) {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let body = match parse_body(name, &input.data, &options, Derive::Debug) {
+        Ok(body) => body,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    // Fields are compared by their Debug output unless their type implements
+    // DebugDiff, so the Debug bounds serve here too.
+    let generics = add_trait_bounds(&input.generics, &body, &options, Derive::Debug);
+
+    impl_debug_diff(&body, name, &generics).into()
+}
+
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive_display(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+
+    let options = match parse_options(&input.attrs, Derive::Display) {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let body = match parse_body(name, &input.data, &options, Derive::Display) {
+        Ok(body) => body,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let generics = add_trait_bounds(&input.generics, &body, &options, Derive::Display);
+
+    match impl_display(&body, name, &generics) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// Pattern destructuring `self` into the bindings of the fields for which
+/// `bind` holds. The others are matched with `_` so that they do not leave
+/// unused bindings.
+fn variant_pattern(
+    variant: &VariantInfo,
+    path: proc_macro2::TokenStream,
+    bind: impl Fn(usize, &FieldInfo) -> bool,
+) -> proc_macro2::TokenStream {
+    variant_pattern_as(variant, path, bind, |f| f.binding.clone())
+}
+
+/// `variant_pattern` with bindings named by `binding` rather than the fields'
+/// own, for destructuring a second value alongside `self`.
+fn variant_pattern_as(
+    variant: &VariantInfo,
+    path: proc_macro2::TokenStream,
+    bind: impl Fn(usize, &FieldInfo) -> bool,
+    binding: impl Fn(&FieldInfo) -> Ident,
+) -> proc_macro2::TokenStream {
+    let bindings = variant.fields.iter().enumerate().map(|(i, f)| {
+        if bind(i, f) {
+            let binding = binding(f);
+            quote!(#binding)
+        } else {
+            quote!(_)
+        }
+    });
+    match variant.style {
+        Style::Named => {
+            let names = variant.fields.iter().map(|f| f.name);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Style::Tuple => quote!(#path(#(#bindings),*)),
+        Style::Unit => quote!(#path),
+    }
+}
+
+/// Pattern and formatting code for one struct or enum variant.
+fn fmt_arm(
+    variant: &VariantInfo,
+    path: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match &variant.template {
+        Some(template) => template_arm(variant, template, path),
+        // Forwarding keeps the formatter's flags, `{:#?}` included.
+        None if variant.transparent => {
+            let value = field_value(&variant.fields[0]);
+            (
+                variant_pattern(variant, path, |_, _| true),
+                quote!(::core::fmt::Debug::fmt(#value, f)),
+            )
+        }
+        None => (
+            variant_pattern(variant, path, |_, f| !f.skip),
+            fmt_variant(variant),
+        ),
+    }
+}
+
+/// Pattern and `write!` of a variant formatted through a template, binding
+/// only the fields the template names.
+fn template_arm(
+    variant: &VariantInfo,
+    template: &Template,
+    path: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let pattern = variant_pattern(variant, path, |i, _| template.uses(i));
+    let format = &template.format;
+    let bindings = variant
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| template.uses(*i))
+        .map(|(_, f)| &f.binding);
+    let fmt = quote! {
+        ::core::write!(f, #format, #(#bindings = #bindings),*)
+    };
+    (pattern, fmt)
+}
+
+/// The `&dyn Debug` passed to the builder for a field. Redaction wins over
+/// any other formatting attribute.
+fn field_value(f: &FieldInfo) -> proc_macro2::TokenStream {
+    field_value_of(f, &f.binding)
+}
+
+/// `field_value` for the field's value bound to `binding`.
+fn field_value_of(f: &FieldInfo, binding: &Ident) -> proc_macro2::TokenStream {
+    match &f.redact {
+        Some(Redact::Full) => return quote!(&::derive_debug::__private::redact(#binding)),
+        Some(Redact::Partial) => {
+            return quote!(&::derive_debug::__private::redact_partial(#binding))
+        }
+        Some(Redact::Hash) => return quote!(&::derive_debug::__private::redact_hash(#binding)),
+        None => {}
+    }
+    match (&f.formatter, &f.format) {
+        (Some(FieldFormatter::With(path)), _) => {
+            quote!(&::derive_debug::__private::DebugWith(#binding, #path))
+        }
+        (Some(FieldFormatter::Display), _) => {
+            quote!(&::derive_debug::__private::DebugDisplay(#binding))
+        }
+        (Some(FieldFormatter::Hex), _) => quote!(&::derive_debug::__private::DebugHex(#binding)),
+        (Some(FieldFormatter::Binary), _) => {
+            quote!(&::derive_debug::__private::DebugBinary(#binding))
+        }
+        (Some(FieldFormatter::Pointer), _) => {
+            quote!(&::derive_debug::__private::DebugPointer(#binding))
+        }
+        (Some(FieldFormatter::CStr), _) => quote!(&::derive_debug::__private::DebugCStr(#binding)),
+        // The wrapper picks a format once it sees the formatter's flags. The
+        // formatters above cannot be combined with `alt_format`.
+        (None, format) if f.alt_format.is_some() => {
+            let compact = match format {
+                Some(str_lit) => {
+                    let s = str_lit.value();
+                    quote!(&::core::format_args!(#s, #binding))
+                }
+                None => quote!(#binding),
+            };
+            let alt = f.alt_format.as_ref().map(LitStr::value);
+            quote! {
+                &::derive_debug::__private::DebugAlternate(
+                    #compact,
+                    &::core::format_args!(#alt, #binding),
+                )
+            }
+        }
+        (None, Some(str_lit)) => {
+            let s = str_lit.value();
+            quote!(&::core::format_args!(#s, #binding))
+        }
+        (None, None) if f.sorted => {
+            let sorted = match f.collection {
+                Some(Collection::Map) => quote!(SortedMap),
+                Some(Collection::Set) => quote!(SortedSet),
+                _ => quote!(SortedList),
+            };
+            let max = match f.max_items {
+                Some(max) => quote!(#max),
+                None => quote!(::core::primitive::usize::MAX),
+            };
+            quote!(&::derive_debug::__private::#sorted(#binding, #max))
+        }
+        (None, None) => match (f.max_items, f.max_len) {
+            (Some(max), _) => {
+                let truncated = match f.collection {
+                    Some(Collection::Map) => quote!(TruncatedMap),
+                    Some(Collection::Set) => quote!(TruncatedSet),
+                    _ => quote!(TruncatedList),
+                };
+                quote!(&::derive_debug::__private::#truncated(#binding, #max))
+            }
+            (None, Some(max)) => quote!(&::derive_debug::__private::TruncatedStr(#binding, #max)),
+            (None, None) => quote!(#binding),
+        },
+    }
+}
+
+/// Where the entries of a struct or variant are sent. Either way `__skipped`
+/// records whether a `skip_if`, or a flattened struct, left anything out.
+#[derive(Clone, Copy)]
+enum Sink {
+    /// The `__debug` builder of `Debug::fmt`.
+    Builder,
+    /// The `__visit` callback of `DebugFields::visit_fields`.
+    Visitor,
+}
+
+/// Statement passing a field to `sink`.
+fn field_entry(f: &FieldInfo, sink: Sink) -> proc_macro2::TokenStream {
+    let binding = &f.binding;
+    let value = field_value(f);
+    let field = match (f.name, sink) {
+        // The inner struct passes on its own fields, and says whether it
+        // left any out.
+        (Some(name), Sink::Builder) if f.flatten => quote_spanned! {name.span()=>
+            __skipped |= ::derive_debug::DebugFields::debug_fields(#binding, __debug);
+        },
+        (Some(name), Sink::Visitor) if f.flatten => quote_spanned! {name.span()=>
+            __skipped |= ::derive_debug::DebugFields::visit_fields(#binding, __visit);
+        },
+        (Some(name), _) => {
+            let name_string = match &f.rename {
+                Some(rename) => rename.value(),
+                None => name.to_string(),
+            };
+            match sink {
+                Sink::Builder => quote_spanned! {name.span()=>
+                    __debug.field(#name_string, #value);
+                },
+                Sink::Visitor => quote_spanned! {name.span()=>
+                    __visit(#name_string, #value);
+                },
+            }
+        }
+        // Only structs with named fields are visited.
+        (None, _) => quote_spanned! {f.ty.span()=>
+            __debug.field(#value);
+        },
+    };
+    match &f.skip_if {
+        Some(skip_if) => quote! {
+            if #skip_if(#binding) {
+                __skipped = true;
+            } else {
+                #field
+            }
+        },
+        None => field,
+    }
+}
+
+/// Statements passing the fields and `extra` entries of a variant to `sink`,
+/// followed by `finish`. That is given whether anything was left out, or
+/// `None` if this is only known at runtime, from `__skipped`.
+fn variant_entries(
+    variant: &VariantInfo,
+    sink: Sink,
+    finish: impl FnOnce(Option<bool>) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let entries = variant
+        .fields
+        .iter()
+        .filter(|f| !f.skip)
+        .map(|f| field_entry(f, sink))
+        .chain(extra_entries(variant, sink));
+
+    // Whether anything was left out is known up front for `skip`, but only at
+    // runtime for `skip_if` and flattened structs.
+    let skipped = variant.fields.iter().any(|f| f.skip);
+    let at_runtime = variant
+        .fields
+        .iter()
+        .any(|f| f.skip_if.is_some() || (f.flatten && !f.skip));
+    if at_runtime {
+        let finish = finish(None);
+        quote! {
+            let mut __skipped = #skipped;
+            #(#entries)*
+            #finish
+        }
+    } else {
+        let finish = finish(Some(skipped));
+        quote! {
+            #(#entries)*
+            #finish
+        }
+    }
+}
+
+/// Statements passing the `extra` entries of a struct to `sink`. Errors in
+/// an expression point at its string.
+fn extra_entries<'a>(
+    variant: &'a VariantInfo,
+    sink: Sink,
+) -> impl Iterator<Item = proc_macro2::TokenStream> + 'a {
+    variant.extra.iter().map(move |extra| {
+        let name = &extra.name;
+        let expr = &extra.expr;
+        match sink {
+            Sink::Builder => quote_spanned! {expr.span()=>
+                __debug.field(#name, &(#expr));
+            },
+            Sink::Visitor => quote_spanned! {expr.span()=>
+                __visit(#name, &(#expr));
+            },
+        }
+    })
+}
+
+fn fmt_variant(variant: &VariantInfo) -> proc_macro2::TokenStream {
+    let variant_name = &variant.name;
+
+    let finish = variant_entries(variant, Sink::Builder, |skipped| match skipped {
+        Some(false) => quote!(__debug.finish()),
+        Some(true) => quote!(__debug.finish_non_exhaustive()),
+        None => quote! {
+            if __skipped {
+                __debug.finish_non_exhaustive()
+            } else {
+                __debug.finish()
+            }
+        },
+    });
+    match variant.style {
+        // Flattened fields are handed the builder by reference.
+        Style::Named => quote! {
+            let mut __debug = f.debug_struct(#variant_name);
+            let __debug = &mut __debug;
+            #finish
+        },
+        Style::Tuple => quote! {
+            let mut __debug = f.debug_tuple(#variant_name);
+            #finish
+        },
+        Style::Unit => quote! {
+            f.write_str(#variant_name)
+        },
+    }
+}
+
+fn impl_debug(
+    body: &Body,
+    name: &Ident,
+    generics: &Generics,
+    max_depth: Option<usize>,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Every derived value counts as a level for `max_depth`, except one that
+    // forwards to its only field. The guard leaves the level when dropped.
+    let max_depth = match max_depth {
+        Some(max_depth) => quote!(::derive_debug::__private::max_depth::<Self>(#max_depth)),
+        None => quote!(::core::option::Option::None),
+    };
+    let enter_depth = match body {
+        Body::Struct(variant) if variant.transparent => quote!(),
+        _ => quote! {
+            let __depth = ::derive_debug::__private::enter_depth(#max_depth);
+            if __depth.is_none() {
+                return f.write_str("..");
+            }
+        },
+    };
+
+    let fmt_body = match body {
+        Body::Struct(variant) => {
+            let (pattern, fmt_variant) = fmt_arm(variant, quote!(Self));
+            quote! {
+                let #pattern = self;
+                #fmt_variant
+            }
+        }
+        // The user vouches for the chosen field being the one the union holds.
+        Body::Union(variant) => {
+            let reads = variant.fields.iter().filter(|f| !f.skip).map(|f| {
+                let binding = &f.binding;
+                let name = f.name;
+                quote!(let #binding = unsafe { &self.#name };)
+            });
+            let fmt_variant = fmt_variant(variant);
+            quote! {
+                #(#reads)*
+                #fmt_variant
+            }
+        }
+        // An empty enum has no values to format.
+        Body::Enum(variants) if variants.is_empty() => quote!(match *self {}),
+        Body::Enum(variants) => {
+            let arms = variants.iter().map(|v| {
+                let ident = v.ident;
+                let (pattern, fmt_variant) = fmt_arm(v, quote!(Self::#ident));
+                quote! {
+                    #pattern => {
+                        #fmt_variant
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+    quote! {
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #enter_depth
+                #fmt_body
+            }
+        }
+    }
+}
+
+/// `DebugFields` for a struct with named fields, visiting its entries as they
+/// appear in its Debug output so that loggers can record them one by one and
+/// a parent can flatten them into its own output. Skipped fields are left out,
+/// and reported as such.
+fn impl_debug_fields(body: &Body, name: &Ident, generics: &Generics) -> proc_macro2::TokenStream {
+    let variant = match body {
+        Body::Struct(v) if v.template.is_none() && !v.transparent => v,
+        _ => return quote!(),
+    };
+    if !matches!(variant.style, Style::Named) {
+        return quote!();
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let pattern = variant_pattern(variant, quote!(Self), |_, f| !f.skip);
+    let entries = variant_entries(variant, Sink::Visitor, |skipped| match skipped {
+        Some(skipped) => quote!(#skipped),
+        None => quote!(__skipped),
+    });
+    quote! {
+        impl #impl_generics ::derive_debug::DebugFields for #name #ty_generics #where_clause {
+            fn visit_fields(
+                &self,
+                __visit: &mut dyn ::core::ops::FnMut(
+                    &'static ::core::primitive::str,
+                    &dyn ::core::fmt::Debug,
+                ),
+            ) -> ::core::primitive::bool {
+                let #pattern = self;
+                #entries
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use syn::{Attribute, Error, Expr, LitStr, Meta, MetaList, NestedMeta, WherePredicate};
+
+use super::fields::{
+    check_supported, helper_attrs, lit_str, lit_usize, parse_bound, parse_variant_attr,
+    unknown_key, Derive, VariantAttrs,
+};
+
+type Option<T> = std::option::Option<T>;
+
+/// Struct-level `#[debug(...)]` or `#[display(...)]` settings.
+#[derive(Default)]
+pub struct DebugOptions {
+    /// `#[debug(name = "...")]`, printed in place of the struct's name.
+    pub name: Option<LitStr>,
+    pub bound: Option<Vec<WherePredicate>>,
+    /// Default `max_items` for fields of a recognised collection type.
+    pub max_items: Option<usize>,
+    /// Default `max_len` for fields of a recognised string type.
+    pub max_len: Option<usize>,
+    /// `#[debug(max_depth = N)]`, the levels of derived values printed
+    /// inside this one, itself included, before they are elided as `..`.
+    pub max_depth: Option<usize>,
+    /// Sort the output of every `HashMap` and `HashSet` field.
+    pub sorted_collections: bool,
+    /// Template or `transparent` replacing the derived output of a struct.
+    pub variant: VariantAttrs,
+    /// `#[debug(extra(...))]` entries printed after the fields.
+    pub extra: Vec<Extra>,
+    /// `#[debug(union_field = "...")]`, the field of a union to print.
+    pub union_field: Option<LitStr>,
+}
+
+/// Keys of `#[debug(...)]` on a struct or enum.
+const OPTION_KEYS: &[&str] = &[
+    "bound",
+    "extra",
+    "max_depth",
+    "max_items",
+    "max_len",
+    "name",
+    "sorted_collections",
+    "transparent",
+    "union_field",
+];
+
+/// Keys of `#[display(...)]` on a struct or enum.
+const DISPLAY_OPTION_KEYS: &[&str] = &["bound", "transparent"];
+
+/// A computed entry, `#[debug(extra(name = "len", expr = "self.buf.len()"))]`.
+#[derive(Clone)]
+pub struct Extra {
+    pub name: LitStr,
+    /// Parsed from the `expr` string, whose span its tokens carry.
+    pub expr: Expr,
+}
+
+fn parse_extra(list: &MetaList) -> syn::Result<Extra> {
+    let mut name = Option::None;
+    let mut expr = Option::None;
+    for nested in &list.nested {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                name = Option::Some(lit_str(&nv.lit)?.clone());
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("expr") => {
+                let lit = lit_str(&nv.lit)?;
+                // Unbalanced delimiters fail to lex at all, which syn would
+                // report at the derive rather than at the string.
+                let parsed = lit.parse().map_err(|e| {
+                    Error::new_spanned(lit, format!("`expr` is not a valid expression: {}", e))
+                })?;
+                expr = Option::Some(parsed);
+            }
+            nested => return Err(unknown_key(nested, "extra option", &["expr", "name"])),
+        }
+    }
+    match (name, expr) {
+        (Option::Some(name), Option::Some(expr)) => Ok(Extra { name, expr }),
+        _ => Err(Error::new_spanned(
+            list,
+            r#"expected `extra(name = "...", expr = "...")`"#,
+        )),
+    }
+}
+
+pub fn parse_options(attrs: &[Attribute], derive: Derive) -> syn::Result<DebugOptions> {
+    let mut options = DebugOptions::default();
+    for attr in helper_attrs(attrs, derive) {
+        match attr.parse_meta()? {
+            Meta::List(l) => {
+                for nested in l.nested {
+                    if derive == Derive::Display {
+                        check_supported(&nested, derive, OPTION_KEYS, DISPLAY_OPTION_KEYS)?;
+                    }
+                    if parse_variant_attr(&mut options.variant, &nested) {
+                        continue;
+                    }
+                    match nested {
+                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                            options.name = Option::Some(lit_str(&nv.lit)?.clone());
+                        }
+                        NestedMeta::Meta(Meta::NameValue(nv))
+                            if nv.path.is_ident("union_field") =>
+                        {
+                            options.union_field = Option::Some(lit_str(&nv.lit)?.clone());
+                        }
+                        NestedMeta::Meta(Meta::List(l)) if l.path.is_ident("extra") => {
+                            options.extra.push(parse_extra(&l)?);
+                        }
+                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("bound") => {
+                            options.bound = Option::Some(parse_bound(&nv.lit)?);
+                        }
+                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_items") => {
+                            options.max_items = Option::Some(lit_usize(&nv.lit)?);
+                        }
+                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_depth") => {
+                            // The value itself always prints, so 0 would act as 1.
+                            let max_depth = lit_usize(&nv.lit)?;
+                            if max_depth == 0 {
+                                return Err(Error::new_spanned(
+                                    &nv.lit,
+                                    "`max_depth` must be at least 1, the value itself",
+                                ));
+                            }
+                            options.max_depth = Option::Some(max_depth);
+                        }
+                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_len") => {
+                            options.max_len = Option::Some(lit_usize(&nv.lit)?);
+                        }
+                        NestedMeta::Meta(Meta::Path(p)) if p.is_ident("sorted_collections") => {
+                            options.sorted_collections = true;
+                        }
+                        nested => {
+                            return Err(unknown_key(
+                                &nested,
+                                &format!("{} option", derive.attr()),
+                                match derive {
+                                    Derive::Debug => OPTION_KEYS,
+                                    Derive::Display => DISPLAY_OPTION_KEYS,
+                                },
+                            ))
+                        }
+                    }
+                }
+            }
+            m => {
+                return Err(Error::new_spanned(
+                    m,
+                    format!("expected `{}(...)`", derive.attr()),
+                ))
+            }
+        }
+    }
+    Ok(options)
+}
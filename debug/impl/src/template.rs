@@ -0,0 +1,213 @@
+use syn::{parse_quote, Error, LitStr, Path};
+
+use super::fields::{template_ignored_key, FieldInfo};
+
+/// A `#[debug("...")]` format string whose `{field}` placeholders have been
+/// rewritten to the bindings of the fields they name.
+pub struct Template {
+    pub format: String,
+    /// Index into the variant's fields of each placeholder, with the
+    /// formatting trait its format spec selects.
+    pub args: Vec<(usize, Path)>,
+}
+
+impl Template {
+    /// Whether the field at `index` is referenced by any placeholder.
+    pub fn uses(&self, index: usize) -> bool {
+        self.args.iter().any(|(i, _)| *i == index)
+    }
+}
+
+fn find_field(fields: &[FieldInfo], arg: &str) -> Option<usize> {
+    match arg.parse::<usize>() {
+        Ok(index) => (index < fields.len() && fields[index].name.is_none()).then_some(index),
+        Err(_) => fields
+            .iter()
+            .position(|f| f.name.as_ref().is_some_and(|name| name == arg)),
+    }
+}
+
+/// The trait a placeholder formats through, from the type at the end of its
+/// format spec: `{x}` is Display, `{x:?}` Debug, `{x:#x}` LowerHex and so on.
+fn spec_trait(spec: &str) -> Path {
+    if spec.ends_with('?') {
+        return parse_quote!(::core::fmt::Debug);
+    }
+    match spec.chars().last() {
+        Some('x') => parse_quote!(::core::fmt::LowerHex),
+        Some('X') => parse_quote!(::core::fmt::UpperHex),
+        Some('o') => parse_quote!(::core::fmt::Octal),
+        Some('b') => parse_quote!(::core::fmt::Binary),
+        Some('e') => parse_quote!(::core::fmt::LowerExp),
+        Some('E') => parse_quote!(::core::fmt::UpperExp),
+        Some('p') => parse_quote!(::core::fmt::Pointer),
+        _ => parse_quote!(::core::fmt::Display),
+    }
+}
+
+/// Checks a format spec, the part of a placeholder after the `:`, against the
+/// grammar of `std::fmt`. Widths and precisions taken from other arguments are
+/// rejected, since only the fields are passed to the format string.
+fn check_spec(spec: &str) -> Result<(), String> {
+    let chars = spec.chars().collect::<Vec<_>>();
+    let mut i = 0;
+    let is_align = |c: Option<&char>| matches!(c, Some('<' | '^' | '>'));
+    if is_align(chars.get(1)) {
+        i = 2;
+    } else if is_align(chars.first()) {
+        i = 1;
+    }
+    if matches!(chars.get(i), Some('+' | '-')) {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'#') {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'0') {
+        i += 1;
+    }
+    let digits = |i: &mut usize| {
+        let start = *i;
+        while chars.get(*i).is_some_and(char::is_ascii_digit) {
+            *i += 1;
+        }
+        *i > start
+    };
+    digits(&mut i);
+    if chars.get(i) == Some(&'.') {
+        i += 1;
+        if chars.get(i) == Some(&'*') {
+            return Err(format!("`.*` in `{{:{}}}` is not supported here", spec));
+        }
+        if !digits(&mut i) {
+            return Err(format!("missing precision after `.` in `{{:{}}}`", spec));
+        }
+    }
+    match &spec[chars[..i].iter().map(|c| c.len_utf8()).sum::<usize>()..] {
+        "" | "?" | "x?" | "X?" | "x" | "X" | "o" | "b" | "e" | "E" | "p" => Ok(()),
+        rest if rest.contains('$') => Err(format!(
+            "width and precision arguments in `{{:{}}}` are not supported here",
+            spec
+        )),
+        rest => Err(format!(
+            "unknown format type `{}` in `{{:{}}}`, expected one of `?`, `x`, `X`, `o`, `b`, \
+             `e`, `E` or `p`",
+            rest, spec
+        )),
+    }
+}
+
+/// Walks the placeholders of a format string, checking their specs and
+/// replacing each `{arg:spec}` with what `rewrite` returns for it.
+fn scan(
+    lit: &LitStr,
+    mut rewrite: impl FnMut(&str, &str) -> Result<String, String>,
+) -> syn::Result<String> {
+    let error = |message: String| Error::new_spanned(lit, message);
+    let value = lit.value();
+    let mut format = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(error("unterminated `{` in format string".to_owned())),
+                    }
+                }
+                let (arg, spec) = match placeholder.split_once(':') {
+                    Some((arg, spec)) => (arg.trim(), spec),
+                    None => (placeholder.trim(), ""),
+                };
+                check_spec(spec).map_err(error)?;
+                format.push_str(&rewrite(arg, spec).map_err(error)?);
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '}' => return Err(error("unmatched `}` in format string".to_owned())),
+            c => format.push(c),
+        }
+    }
+    Ok(format)
+}
+
+/// Checks the format string of a field, which is passed the field's value as
+/// its only argument.
+pub fn check_field_format(lit: &LitStr) -> syn::Result<()> {
+    let mut used = false;
+    scan(lit, |arg, _| {
+        if !arg.is_empty() && arg != "0" {
+            return Err(format!(
+                "the only argument of a field format is the field's value, not `{}`",
+                arg
+            ));
+        }
+        if used && arg.is_empty() {
+            return Err("a field format takes a single argument, the field's value".to_owned());
+        }
+        used = true;
+        Ok(String::new())
+    })?;
+    if !used {
+        return Err(Error::new_spanned(
+            lit,
+            "format string does not use the field's value, as in `{:?}`",
+        ));
+    }
+    Ok(())
+}
+
+/// The traits the placeholders of a field format, already checked by
+/// `check_field_format`, select.
+pub fn field_format_traits(lit: &LitStr) -> Vec<Path> {
+    let mut traits = Vec::new();
+    let _ = scan(lit, |_, spec| {
+        traits.push(spec_trait(spec));
+        Ok(String::new())
+    });
+    traits
+}
+
+/// Resolves the placeholders of `lit` against `fields`, named fields by name
+/// and tuple fields by index. Errors span the template string.
+pub fn parse_template(lit: &LitStr, owner: &str, fields: &[FieldInfo]) -> syn::Result<Template> {
+    let mut args = Vec::new();
+    let format = scan(lit, |arg, spec| {
+        if arg.is_empty() {
+            return Err("template placeholders must name a field, as in `{field}`".to_owned());
+        }
+        let index = match find_field(fields, arg) {
+            Some(index) => index,
+            None => return Err(format!("no field `{}` on `{}`", arg, owner)),
+        };
+        if fields[index].redact.is_some() {
+            return Err(format!(
+                "field `{}` is redacted and cannot appear in a template",
+                arg
+            ));
+        }
+        if let Some(key) = template_ignored_key(&fields[index]) {
+            return Err(format!(
+                "`{}` on field `{}` would be ignored by the template",
+                key, arg
+            ));
+        }
+        args.push((index, spec_trait(spec)));
+        let binding = &fields[index].binding;
+        Ok(if spec.is_empty() {
+            format!("{{{}}}", binding)
+        } else {
+            format!("{{{}:{}}}", binding, spec)
+        })
+    })?;
+    Ok(Template { format, args })
+}
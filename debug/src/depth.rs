@@ -0,0 +1,56 @@
+//! Depth tracking for `#[debug(max_depth = N)]`.
+//!
+//! Every derived Debug impl enters a level on the current thread while it
+//! formats. A type with `max_depth` caps how many further levels may nest
+//! inside it, and the impls past the cap print `..` instead of their value.
+//!
+//! The depth lives in thread-local storage, so without the `std` feature
+//! nothing is tracked, and `max_depth` is rejected at compile time by
+//! `requires::max_depth`.
+
+#[cfg(feature = "std")]
+use std::cell::Cell;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// Derived impls currently formatting on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Depth at which derived impls stop formatting.
+    static LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Leaves the level entered by `enter_depth` when dropped, even if formatting
+/// panicked.
+pub struct DepthGuard {
+    #[cfg(feature = "std")]
+    limit: usize,
+}
+
+#[cfg(feature = "std")]
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+        LIMIT.with(|limit| limit.set(self.limit));
+    }
+}
+
+/// Enters a derived Debug impl whose type allows `max_depth` levels, itself
+/// included, or returns `None` if the value is nested too deep to print.
+#[cfg(feature = "std")]
+pub fn enter_depth(max_depth: Option<usize>) -> Option<DepthGuard> {
+    let current = DEPTH.with(Cell::get);
+    let limit = LIMIT.with(Cell::get);
+    if current >= limit {
+        return None;
+    }
+    if let Some(max_depth) = max_depth {
+        LIMIT.with(|l| l.set(limit.min(current.saturating_add(max_depth))));
+    }
+    DEPTH.with(|depth| depth.set(current + 1));
+    Some(DepthGuard { limit })
+}
+
+#[cfg(not(feature = "std"))]
+pub fn enter_depth(_max_depth: Option<usize>) -> Option<DepthGuard> {
+    Some(DepthGuard {})
+}
@@ -0,0 +1,183 @@
+//! Support for `#[derive(DebugDiff)]`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
+
+use crate::flatten::DebugFields;
+
+/// A difference between two values at a path of field names, such as
+/// `config.tls.port: 443 -> 8443`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Field names from the compared values down to the difference, joined
+    /// with `.`. Empty if the values differ as a whole, like two different
+    /// variants of an enum.
+    pub path: String,
+    /// Debug output of the left value.
+    pub left: String,
+    /// Debug output of the right value.
+    pub right: String,
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        write!(f, "{} -> {}", self.left, self.right)
+    }
+}
+
+/// Field-by-field comparison, implemented by `#[derive(DebugDiff)]`.
+///
+/// Fields whose types implement `DebugDiff` are compared recursively, and any
+/// other field by its Debug output, honouring the field's `#[debug(...)]`
+/// attributes.
+pub trait DebugDiff: Debug {
+    /// Appends the differences between `self` and `other`, with their paths
+    /// prefixed by `path`.
+    fn debug_diff_into(&self, other: &Self, path: &str, out: &mut Vec<FieldDiff>);
+
+    /// The differences between `self` and `other`, empty if they are equal.
+    fn debug_diff(&self, other: &Self) -> Vec<FieldDiff> {
+        let mut out = Vec::new();
+        self.debug_diff_into(other, "", &mut out);
+        out
+    }
+}
+
+/// Asserts that two values have no `DebugDiff` differences, and otherwise
+/// panics listing them one per line.
+#[macro_export]
+macro_rules! assert_debug_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let diffs = $crate::DebugDiff::debug_diff(left, right);
+                if !diffs.is_empty() {
+                    $crate::__private::diff_failed(&diffs);
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn diff_failed(diffs: &[FieldDiff]) -> ! {
+    let mut message = String::from("assertion failed: values differ");
+    for diff in diffs {
+        message.push_str(&format!("\n    {}", diff));
+    }
+    panic!("{}", message)
+}
+
+/// `path.name`, or `name` at the top level.
+pub fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        String::from(name)
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Compares two values by their Debug output.
+pub fn diff_debug<T: ?Sized + Debug>(path: &str, left: &T, right: &T, out: &mut Vec<FieldDiff>) {
+    let left = format!("{:?}", left);
+    let right = format!("{:?}", right);
+    if left != right {
+        out.push(FieldDiff {
+            path: String::from(path),
+            left,
+            right,
+        });
+    }
+}
+
+/// The entries `value` visits, each with its Debug output.
+fn entries<T: ?Sized + DebugFields>(value: &T) -> Vec<(&'static str, String)> {
+    let mut entries = Vec::new();
+    let _ = value.visit_fields(&mut |name, value| entries.push((name, format!("{:?}", value))));
+    entries
+}
+
+/// Compares two values entry by entry, each by its Debug output. An entry
+/// that only one side visits, left out by a `skip_if`, is shown as
+/// `(skipped)` on the other.
+pub fn diff_entries<T: ?Sized + DebugFields>(
+    path: &str,
+    left: &T,
+    right: &T,
+    out: &mut Vec<FieldDiff>,
+) {
+    let (left, right) = (entries(left), entries(right));
+    let find = |entries: &[(&str, String)], name: &str| {
+        entries
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map_or_else(|| String::from("(skipped)"), |(_, value)| value.clone())
+    };
+    for (name, value) in &left {
+        let other = find(&right, name);
+        if *value != other {
+            out.push(FieldDiff {
+                path: join_path(path, name),
+                left: value.clone(),
+                right: other,
+            });
+        }
+    }
+    for (name, value) in &right {
+        if !left.iter().any(|(n, _)| n == name) {
+            out.push(FieldDiff {
+                path: join_path(path, name),
+                left: String::from("(skipped)"),
+                right: value.clone(),
+            });
+        }
+    }
+}
+
+/// A pair of field values to compare. Method resolution picks `DiffNested`
+/// when the field type implements `DebugDiff`, and only otherwise autorefs to
+/// reach `DiffDebug`.
+pub struct DiffField<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+pub trait DiffNested {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>);
+}
+
+impl<T: ?Sized + DebugDiff> DiffNested for DiffField<'_, T> {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>) {
+        DebugDiff::debug_diff_into(self.0, self.1, path, out)
+    }
+}
+
+pub trait DiffDebug {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>);
+}
+
+impl<T: ?Sized + Debug> DiffDebug for &DiffField<'_, T> {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>) {
+        diff_debug(path, self.0, self.1, out)
+    }
+}
+
+/// A pair of flattened field values, whose differences are reported under
+/// the parent's path. Like `DiffField`, it compares through `DebugDiff` when
+/// the field type implements it, and otherwise entry by entry.
+pub struct DiffFlattened<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+impl<T: ?Sized + DebugDiff> DiffNested for DiffFlattened<'_, T> {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>) {
+        DebugDiff::debug_diff_into(self.0, self.1, path, out)
+    }
+}
+
+impl<T: ?Sized + DebugFields> DiffDebug for &DiffFlattened<'_, T> {
+    fn diff_into(&self, path: &str, out: &mut Vec<FieldDiff>) {
+        diff_entries(path, self.0, self.1, out)
+    }
+}
@@ -0,0 +1,50 @@
+//! Formatting for the raw pointer fields and `#[debug(cstr)]` byte arrays of
+//! FFI structs.
+
+use core::ffi::CStr;
+use core::fmt::{self, Debug, Formatter, Pointer};
+
+/// `*const T` or `*mut T`, whatever `T` is.
+pub trait RawPointer {
+    fn address(&self) -> *const ();
+}
+
+impl<T: ?Sized> RawPointer for *const T {
+    fn address(&self) -> *const () {
+        self.cast()
+    }
+}
+
+impl<T: ?Sized> RawPointer for *mut T {
+    fn address(&self) -> *const () {
+        self.cast_const().cast()
+    }
+}
+
+/// Formats a raw pointer field as its address, or as `null`.
+pub struct DebugPointer<'a, P: ?Sized>(pub &'a P);
+
+impl<P: ?Sized + RawPointer> Debug for DebugPointer<'_, P> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let address = self.0.address();
+        if address.is_null() {
+            f.write_str("null")
+        } else {
+            Pointer::fmt(&address, f)
+        }
+    }
+}
+
+/// Formats a byte buffer as the C string it holds, up to the first NUL. A
+/// buffer without one is printed whole and marked as unterminated.
+pub struct DebugCStr<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized + AsRef<[u8]>> Debug for DebugCStr<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let bytes = self.0.as_ref();
+        match CStr::from_bytes_until_nul(bytes) {
+            Ok(s) => Debug::fmt(s, f),
+            Err(_) => write!(f, "\"{}\" (unterminated)", bytes.escape_ascii()),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Support for fields marked `#[debug(flatten)]`, and for loggers that want
+//! the entries of a struct rather than its formatted output.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::fmt::{Debug, DebugStruct};
+
+/// Visits the named entries of a value, as its Debug output would show them.
+///
+/// CustomDebug implements this for structs with named fields, applying the
+/// same field attributes as its `Debug` impl, so that a logger can record the
+/// entries as key-value pairs and a parent can list them among its own with
+/// `#[debug(flatten)]`. Types with a hand-written `Debug` impl can implement
+/// it too.
+pub trait DebugFields {
+    /// Passes each entry to `visit`, and returns whether any were left out,
+    /// in which case the Debug output ends in `..`.
+    fn visit_fields(&self, visit: &mut dyn FnMut(&'static str, &dyn Debug)) -> bool;
+
+    /// Adds the entries to the `DebugStruct` being built for another value,
+    /// returning whether any were left out so that it can finish with `..`.
+    fn debug_fields(&self, debug: &mut DebugStruct<'_, '_>) -> bool {
+        self.visit_fields(&mut |name, value| {
+            debug.field(name, value);
+        })
+    }
+}
+
+impl<T: ?Sized + DebugFields> DebugFields for &T {
+    fn visit_fields(&self, visit: &mut dyn FnMut(&'static str, &dyn Debug)) -> bool {
+        (**self).visit_fields(visit)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + DebugFields> DebugFields for Box<T> {
+    fn visit_fields(&self, visit: &mut dyn FnMut(&'static str, &dyn Debug)) -> bool {
+        (**self).visit_fields(visit)
+    }
+}
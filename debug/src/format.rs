@@ -0,0 +1,126 @@
+//! Wrappers behind the `#[debug(with = "...")]`, `#[debug(display)]`,
+//! `#[debug(hex)]`, `#[debug(binary)]` and `#[debug(alt_format = "...")]`
+//! field attributes.
+
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// Formats a field through a user-supplied `fn(&T, &mut Formatter)`.
+pub struct DebugWith<'a, T: ?Sized>(pub &'a T, pub fn(&T, &mut Formatter) -> fmt::Result);
+
+impl<T: ?Sized> Debug for DebugWith<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        (self.1)(self.0, f)
+    }
+}
+
+/// Formats a field through its `Display` impl.
+pub struct DebugDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized + Display> Debug for DebugDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+/// Formats a field one way in `{:?}` and another in `{:#?}`.
+pub struct DebugAlternate<C, A>(pub C, pub A);
+
+impl<C: Debug, A: Debug> Debug for DebugAlternate<C, A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if f.alternate() {
+            self.1.fmt(f)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
+
+/// Values that can be printed in hexadecimal or binary: integers as a single
+/// prefixed number, byte sequences as one hex string or a list of bytes.
+///
+/// This is what `#[debug(hex)]` and `#[debug(binary)]` require of a field, and
+/// what a type parameter used in such a field is bounded by. Implement it to
+/// print your own types with those attributes.
+pub trait Radix {
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result;
+    fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result;
+}
+
+macro_rules! impl_radix_int {
+    ($($ty:ty)*) => {$(
+        impl Radix for $ty {
+            fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{:#x}", self)
+            }
+
+            fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{:#b}", self)
+            }
+        }
+    )*};
+}
+
+impl_radix_int!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl Radix for [u8] {
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("0x")?;
+        for b in self {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(DebugBinary))
+            .finish()
+    }
+}
+
+impl<const N: usize> Radix for [u8; N] {
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+        self[..].fmt_hex(f)
+    }
+
+    fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result {
+        self[..].fmt_binary(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Radix for alloc::vec::Vec<u8> {
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+        self[..].fmt_hex(f)
+    }
+
+    fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result {
+        self[..].fmt_binary(f)
+    }
+}
+
+impl<T: ?Sized + Radix> Radix for &T {
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+        (**self).fmt_hex(f)
+    }
+
+    fn fmt_binary(&self, f: &mut Formatter) -> fmt::Result {
+        (**self).fmt_binary(f)
+    }
+}
+
+pub struct DebugHex<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized + Radix> Debug for DebugHex<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt_hex(f)
+    }
+}
+
+pub struct DebugBinary<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized + Radix> Debug for DebugBinary<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt_binary(f)
+    }
+}
@@ -1,57 +1,58 @@
-mod fields;
-
-use proc_macro::TokenStream;
-
-use fields::*;
-use quote::{quote, quote_spanned};
-use syn::{parse_macro_input, DeriveInput, Ident};
-
-#[proc_macro_derive(CustomDebug, attributes(debug))]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-
-    let name = input.ident;
-
-    let fields = parse_fields(&input.data);
-
-    let debug_impl = impl_debug(fields, &name);
-    let tokens = quote! {
-        #debug_impl
+// Crates that have the "proc-macro" crate type are only allowed to export
+// procedural macros, so the CustomDebug, CustomDisplay and DebugDiff derives
+// live in the derive_debug-impl crate and are re-exported from here, next to
+// the small runtime support that the generated impls call into.
+//
+// The generated code refers to `core` only, so that the derives work in
+// `#![no_std]` crates. Without the `alloc` feature the runtime does not link
+// `alloc` either, which leaves out DebugDiff and `#[debug(sorted)]`. The `std`
+// feature adds the thread-local depth tracking behind
+// `#[debug(max_depth = N)]`. Using either attribute without its feature is a
+// compile error naming the feature.
+#![no_std]
+
+#[cfg(feature = "alloc")]
+pub use derive_debug_impl::DebugDiff;
+pub use derive_debug_impl::{CustomDebug, CustomDisplay};
+#[cfg(feature = "alloc")]
+pub use diff::{DebugDiff, FieldDiff};
+pub use flatten::DebugFields;
+pub use format::Radix;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+
+    pub use crate::depth::{enter_depth, DepthGuard};
+    #[cfg(feature = "alloc")]
+    pub use crate::diff::{
+        diff_debug, diff_failed, join_path, DiffDebug, DiffField, DiffFlattened, DiffNested,
     };
-    // eprintln!("Tokens: {}", tokens);
-    tokens.into()
+    pub use crate::ffi::{DebugCStr, DebugPointer};
+    pub use crate::format::{DebugAlternate, DebugBinary, DebugDisplay, DebugHex, DebugWith};
+    pub use crate::redact::{redact, redact_hash, redact_partial};
+    pub use crate::requires::max_depth;
+    #[cfg(not(feature = "alloc"))]
+    pub use crate::requires::{SortedList, SortedMap, SortedSet};
+    #[cfg(feature = "alloc")]
+    pub use crate::sorted::{SortedList, SortedMap, SortedSet};
+    pub use crate::truncate::{TruncatedList, TruncatedMap, TruncatedSet, TruncatedStr};
 }
 
-fn impl_debug(fields: Vec<FieldInfo>, name: &Ident) -> proc_macro2::TokenStream {
-    let struct_name = format!("{}", name);
-
-    let recurse = fields.iter().map(|f| {
-        if let Some(name) = f.name {
-            let name_string = format!("{}", name);
-            match &f.debug_attr {
-                Some((_i, str_lit)) => {
-                    let s = str_lit.value();
-                    quote_spanned! {name.span()=>
-                        .field(#name_string, &format_args!(#s, &self.#name))
-                    }
-                },
-                None => {
-                        quote_spanned! {name.span()=>
-                            .field(#name_string, &self.#name)
-                        }
-                },
-            }
-        } else {
-            quote!()
-        }
-    });
-    quote! {
-        impl std::fmt::Debug for #name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.debug_struct(#struct_name)
-                #(#recurse)*
-                 .finish()
-            }
-        }
-    }
-}
+mod depth;
+#[cfg(feature = "alloc")]
+mod diff;
+mod ffi;
+mod flatten;
+mod format;
+mod redact;
+mod requires;
+#[cfg(feature = "alloc")]
+mod sorted;
+mod truncate;
@@ -0,0 +1,118 @@
+//! Formatting for fields marked `#[debug(redact)]`.
+//!
+//! With the `unredacted-debug` feature enabled, builds with debug assertions
+//! print redacted fields through their own `Debug` impl instead.
+
+use core::fmt::{self, Debug, Display, Write};
+
+const UNREDACTED: bool = cfg!(all(feature = "unredacted-debug", debug_assertions));
+
+/// Number of trailing characters shown by `#[debug(redact = "partial")]`.
+const VISIBLE_SUFFIX: usize = 4;
+
+/// `#[debug(redact)]`: prints `"<redacted>"`.
+pub fn redact<T: ?Sized + Debug>(value: &T) -> impl Debug + '_ {
+    redacted(value, |_, f| Debug::fmt("<redacted>", f))
+}
+
+/// `#[debug(redact = "partial")]`: prints the last four characters of the
+/// value's `Display` output, escaped as in a string's `Debug` output, or
+/// `"<redacted>"` if it is not longer than that.
+pub fn redact_partial<T: ?Sized + Debug + Display>(value: &T) -> impl Debug + '_ {
+    redacted(value, |value, f| {
+        let mut suffix = Suffix::default();
+        write!(suffix, "{}", value)?;
+        if suffix.len <= VISIBLE_SUFFIX {
+            return Debug::fmt("<redacted>", f);
+        }
+        f.write_str("\"****")?;
+        for i in 0..VISIBLE_SUFFIX {
+            let c = suffix.chars[(suffix.len + i) % VISIBLE_SUFFIX];
+            write!(f, "{}", c.escape_debug())?;
+        }
+        f.write_char('"')
+    })
+}
+
+/// `#[debug(redact = "hash")]`: prints a short hash of the value's `Debug`
+/// output, so that equal secrets can be correlated without being revealed.
+///
+/// The hash is an unsalted 32-bit FNV-1a, chosen to be stable across builds,
+/// not to resist guessing: anyone holding the output can recover a
+/// low-entropy secret, such as a PIN or a dictionary password, by hashing
+/// candidates until one matches. Use it only for high-entropy values like
+/// random tokens, and plain `redact` for everything else.
+pub fn redact_hash<T: ?Sized + Debug>(value: &T) -> impl Debug + '_ {
+    redacted(value, |value, f| {
+        let mut hasher = Fnv1a::default();
+        write!(hasher, "{:?}", value)?;
+        write!(f, "\"<redacted:{:08x}>\"", hasher.0)
+    })
+}
+
+struct Redacted<'a, T: ?Sized, F>(&'a T, F);
+
+fn redacted<T, F>(value: &T, fmt: F) -> Redacted<'_, T, F>
+where
+    T: ?Sized,
+    F: Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+{
+    Redacted(value, fmt)
+}
+
+impl<T, F> Debug for Redacted<'_, T, F>
+where
+    T: ?Sized + Debug,
+    F: Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if UNREDACTED {
+            Debug::fmt(self.0, f)
+        } else {
+            (self.1)(self.0, f)
+        }
+    }
+}
+
+/// Keeps the last `VISIBLE_SUFFIX` characters written to it.
+struct Suffix {
+    chars: [char; VISIBLE_SUFFIX],
+    len: usize,
+}
+
+impl Default for Suffix {
+    fn default() -> Self {
+        Suffix {
+            chars: ['\0'; VISIBLE_SUFFIX],
+            len: 0,
+        }
+    }
+}
+
+impl Write for Suffix {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.chars[self.len % VISIBLE_SUFFIX] = c;
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+/// 32-bit FNV-1a, which is stable across builds and platforms.
+struct Fnv1a(u32);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0x811c_9dc5)
+    }
+}
+
+impl Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for b in s.bytes() {
+            self.0 = (self.0 ^ u32::from(b)).wrapping_mul(0x0100_0193);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Attributes that need an optional feature of derive_debug.
+//!
+//! The derive cannot see which features the runtime was built with, so it
+//! emits the same code either way. Without the feature, that code names a
+//! marker trait that nothing implements, and the error says which feature to
+//! enable.
+
+/// Implemented for every type when the `std` feature is enabled.
+#[diagnostic::on_unimplemented(
+    message = "`#[debug(max_depth = N)]` on `{Self}` requires the `std` feature of derive_debug",
+    label = "the depth is tracked per thread, which needs `std`"
+)]
+pub trait MaxDepth {}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized> MaxDepth for T {}
+
+/// The `max_depth` of a type, which is only honoured with the `std` feature.
+pub fn max_depth<T: ?Sized + MaxDepth>(max_depth: usize) -> Option<usize> {
+    Some(max_depth)
+}
+
+/// Bounds the stand-ins for the sorted wrappers, which exist only without
+/// `alloc`, so it has no impls.
+#[cfg(not(feature = "alloc"))]
+#[diagnostic::on_unimplemented(
+    message = "`#[debug(sorted)]` on `{Self}` requires the `alloc` feature of derive_debug",
+    label = "sorting collects the items into a `Vec`, which needs `alloc`"
+)]
+pub trait Sorted {}
+
+#[cfg(not(feature = "alloc"))]
+macro_rules! sorted_stub {
+    ($($name:ident)*) => {$(
+        /// Stands in for the sorted wrapper of the same name without `alloc`.
+        pub struct $name<'a, T: ?Sized + Sorted>(pub &'a T, pub usize);
+
+        impl<T: ?Sized + Sorted> core::fmt::Debug for $name<'_, T> {
+            fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+                unreachable!("`Sorted` has no impls")
+            }
+        }
+    )*};
+}
+
+#[cfg(not(feature = "alloc"))]
+sorted_stub!(SortedList SortedMap SortedSet);
@@ -0,0 +1,53 @@
+//! Formatting for fields marked `#[debug(sorted)]`, which print their items in
+//! order so that the output of hash-based collections is deterministic.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use crate::truncate::{fmt_list, fmt_map, fmt_set};
+
+/// Prints the items of a sequence in sorted order, at most `max` of them.
+pub struct SortedList<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized> Debug for SortedList<'a, T>
+where
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug + Ord,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut items = self.0.into_iter().collect::<Vec<_>>();
+        items.sort();
+        fmt_list(f, items.into_iter(), self.1)
+    }
+}
+
+/// Prints the items of a set in sorted order, at most `max` of them.
+pub struct SortedSet<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized> Debug for SortedSet<'a, T>
+where
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug + Ord,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut items = self.0.into_iter().collect::<Vec<_>>();
+        items.sort();
+        fmt_set(f, items.into_iter(), self.1)
+    }
+}
+
+/// Prints the entries of a map sorted by key, at most `max` of them.
+pub struct SortedMap<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized, K, V> Debug for SortedMap<'a, T>
+where
+    &'a T: IntoIterator<Item = (K, V)>,
+    K: Debug + Ord,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut entries = self.0.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        fmt_map(f, entries.into_iter(), self.1)
+    }
+}
@@ -0,0 +1,122 @@
+//! Formatting for fields limited by `#[debug(max_items = N)]` and
+//! `#[debug(max_len = N)]`.
+
+use core::fmt::{self, Debug, Formatter};
+
+/// The trailing `... (N more)` entry.
+struct More(usize);
+
+impl Debug for More {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "... ({} more)", self.0)
+    }
+}
+
+/// A map entry printed inside `debug_set`, which gives the same `{k: v}`
+/// layout as `debug_map` but also accepts the `More` marker.
+struct Entry<K, V>(K, V);
+
+impl<K: Debug, V: Debug> Debug for Entry<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)?;
+        f.write_str(": ")?;
+        self.1.fmt(f)
+    }
+}
+
+pub(crate) fn fmt_list<I>(f: &mut Formatter, mut iter: I, max: usize) -> fmt::Result
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    let mut list = f.debug_list();
+    list.entries(iter.by_ref().take(max));
+    match iter.count() {
+        0 => {}
+        more => {
+            list.entry(&More(more));
+        }
+    }
+    list.finish()
+}
+
+pub(crate) fn fmt_set<I>(f: &mut Formatter, mut iter: I, max: usize) -> fmt::Result
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    let mut set = f.debug_set();
+    set.entries(iter.by_ref().take(max));
+    match iter.count() {
+        0 => {}
+        more => {
+            set.entry(&More(more));
+        }
+    }
+    set.finish()
+}
+
+pub(crate) fn fmt_map<I, K, V>(f: &mut Formatter, iter: I, max: usize) -> fmt::Result
+where
+    I: Iterator<Item = (K, V)>,
+    K: Debug,
+    V: Debug,
+{
+    fmt_set(f, iter.map(|(k, v)| Entry(k, v)), max)
+}
+
+/// Prints at most `max` items of a sequence as a list.
+pub struct TruncatedList<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized> Debug for TruncatedList<'a, T>
+where
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fmt_list(f, self.0.into_iter(), self.1)
+    }
+}
+
+/// Prints at most `max` items of a set.
+pub struct TruncatedSet<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized> Debug for TruncatedSet<'a, T>
+where
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fmt_set(f, self.0.into_iter(), self.1)
+    }
+}
+
+/// Prints at most `max` entries of a map.
+pub struct TruncatedMap<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<'a, T: ?Sized, K, V> Debug for TruncatedMap<'a, T>
+where
+    &'a T: IntoIterator<Item = (K, V)>,
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fmt_map(f, self.0.into_iter(), self.1)
+    }
+}
+
+/// Prints at most `max` characters of a string.
+pub struct TruncatedStr<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<T: ?Sized + AsRef<str>> Debug for TruncatedStr<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let s = self.0.as_ref();
+        match s.char_indices().nth(self.1) {
+            Some((end, _)) => {
+                Debug::fmt(&s[..end], f)?;
+                write!(f, "{:?}", More(s[end..].chars().count()))
+            }
+            None => Debug::fmt(s, f),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Besides structs with named fields, CustomDebug handles tuple structs, unit
+// structs and enums, formatting each variant the way the standard derive
+// does. Field-level `#[debug = "..."]` formats apply inside variants too.
+//
+//     match self {
+//         Self::Connected { addr, retries } => f
+//             .debug_struct("Connected")
+//             .field("addr", addr)
+//             .field("retries", &format_args!("{}x", retries))
+//             .finish(),
+//         Self::Failed(code) => f.debug_tuple("Failed").field(code).finish(),
+//         Self::Idle => f.write_str("Idle"),
+//     }
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub enum State<T> {
+    Connected {
+        addr: T,
+        #[debug = "{}x"]
+        retries: u8,
+    },
+    Failed(#[debug = "{:#06x}"] u16),
+    Idle,
+}
+
+#[derive(CustomDebug)]
+pub struct Meters(f64, #[debug = "0b{:04b}"] u8);
+
+#[derive(CustomDebug)]
+pub struct Marker;
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn main() {
+    let connected = State::Connected {
+        addr: "localhost",
+        retries: 3,
+    };
+    assert_eq!(
+        format!("{:?}", connected),
+        r#"Connected { addr: "localhost", retries: 3x }"#,
+    );
+    assert_eq!(
+        format!("{:?}", State::<()>::Failed(404)),
+        "Failed(0x0194)",
+    );
+    assert_eq!(format!("{:?}", State::<()>::Idle), "Idle");
+
+    assert_eq!(format!("{:?}", Meters(1.5, 5)), "Meters(1.5, 0b0101)");
+    assert_eq!(format!("{:#?}", Meters(1.5, 5)), "Meters(\n    1.5,\n    0b0101,\n)");
+    assert_eq!(format!("{:?}", Marker), "Marker");
+
+    fn assert_debug<F: std::fmt::Debug>() {}
+    assert_debug::<Never>();
+}
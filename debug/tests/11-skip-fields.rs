@@ -0,0 +1,56 @@
+// Fields marked `#[debug(skip)]` are left out of the output, and fields marked
+// `#[debug(skip_if = "path")]` are left out whenever `path(&field)` returns
+// true. When anything was left out the output ends in `..`, as produced by
+// `finish_non_exhaustive()`.
+//
+// Skipped fields contribute no trait bounds, so a type parameter that only
+// appears in skipped fields need not implement Debug.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Connection<C> {
+    addr: &'static str,
+    #[debug(skip)]
+    callback: C,
+    #[debug(skip_if = "Option::is_none")]
+    user: Option<&'static str>,
+}
+
+#[derive(CustomDebug)]
+pub struct Handle(u32, #[debug(skip)] fn());
+
+#[derive(CustomDebug)]
+pub enum Event {
+    Message {
+        #[debug(skip_if = "str::is_empty")]
+        text: &'static str,
+    },
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+    assert_debug::<Connection<NotDebug>>();
+
+    let mut conn = Connection {
+        addr: "localhost",
+        callback: NotDebug,
+        user: None,
+    };
+    assert_eq!(format!("{:?}", conn), r#"Connection { addr: "localhost", .. }"#);
+    conn.user = Some("root");
+    assert_eq!(
+        format!("{:?}", conn),
+        r#"Connection { addr: "localhost", user: Some("root"), .. }"#,
+    );
+
+    assert_eq!(format!("{:?}", Handle(7, main)), "Handle(7, ..)");
+
+    let event = Event::Message { text: "" };
+    assert_eq!(format!("{:?}", event), "Message { .. }");
+    let event = Event::Message { text: "hi" };
+    assert_eq!(format!("{:?}", event), r#"Message { text: "hi" }"#);
+}
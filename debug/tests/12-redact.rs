@@ -0,0 +1,66 @@
+// Secrets can be kept out of Debug output with `#[debug(redact)]`:
+//
+//   - `redact` prints "<redacted>" in place of the value,
+//   - `redact = "partial"` shows only the last four characters of the value's
+//     Display output,
+//   - `redact = "hash"` prints a stable short hash, so that equal secrets can
+//     be correlated across log lines. The hash is unsalted and only 32 bits,
+//     so it is for high-entropy secrets such as random tokens; a guessable
+//     password can be found by hashing candidates.
+//
+// Enabling the `unredacted-debug` feature of derive_debug prints redacted
+// fields in full, but only in builds with debug assertions.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Credentials {
+    user: &'static str,
+    #[debug(redact)]
+    password: String,
+    #[debug(redact = "partial")]
+    card: u64,
+    #[debug(redact = "hash")]
+    token: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub struct Account {
+    #[debug(redact = "partial")]
+    iban: String,
+}
+
+const UNREDACTED: bool = cfg!(all(feature = "unredacted-debug", debug_assertions));
+
+fn main() {
+    let creds = Credentials {
+        user: "root",
+        password: "hunter2".to_owned(),
+        card: 4111111111111234,
+        token: "abc",
+    };
+    let expected = if UNREDACTED {
+        r#"Credentials { user: "root", password: "hunter2", card: 4111111111111234, token: "abc" }"#
+    } else {
+        r#"Credentials { user: "root", password: "<redacted>", card: "****1234", token: "<redacted:b6413aeb>" }"#
+    };
+    assert_eq!(format!("{:?}", creds), expected);
+
+    let short = Credentials {
+        card: 42,
+        ..creds
+    };
+    let expected = if UNREDACTED { "card: 42" } else { r#"card: "<redacted>""# };
+    assert!(format!("{:?}", short).contains(expected));
+
+    // The visible characters are escaped like the rest of a string.
+    let quoted = Account {
+        iban: "DE00€€x\"".to_owned(),
+    };
+    let expected = if UNREDACTED {
+        r#"Account { iban: "DE00€€x\"" }"#
+    } else {
+        r#"Account { iban: "****€€x\"" }"#
+    };
+    assert_eq!(format!("{:?}", quoted), expected);
+}
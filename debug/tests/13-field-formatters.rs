@@ -0,0 +1,132 @@
+// A field's own Debug impl can be replaced:
+//
+//   - `#[debug(with = "path")]` calls `path(&field, formatter)`, where `path`
+//     is a `fn(&T, &mut fmt::Formatter) -> fmt::Result`,
+//   - `#[debug(display)]` uses the field's Display impl,
+//   - `#[debug(hex)]` and `#[debug(binary)]` print integers in that radix,
+//     and byte arrays, slices and vectors as a hex string or a list of bytes.
+//
+// A type parameter used in a `with` field needs no Debug bound, and one used
+// in a `display` field is bounded by Display instead. Likewise a field format
+// bounds the field by the trait each of its placeholders uses, and `hex` or
+// `binary` by `derive_debug::Radix`, which other types can implement too.
+
+use derive_debug::{CustomDebug, Radix};
+use std::fmt::{self, Debug, Display};
+use std::time::Duration;
+
+mod fmt_util {
+    use std::fmt;
+    use std::time::Duration;
+
+    pub fn duration(d: &Duration, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}ms", d.as_millis())
+    }
+
+    pub fn opaque<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("<opaque>")
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Packet<H, N> {
+    #[debug(with = "fmt_util::duration")]
+    elapsed: Duration,
+    #[debug(with = "fmt_util::opaque")]
+    handle: H,
+    #[debug(display)]
+    name: N,
+    #[debug(hex)]
+    flags: u16,
+    #[debug(binary)]
+    mode: u8,
+    #[debug(hex)]
+    payload: Vec<u8>,
+    #[debug(hex)]
+    checksum: [u8; 2],
+    #[debug(binary)]
+    tail: &'static [u8],
+}
+
+#[derive(CustomDebug)]
+pub struct Labelled<L, C> {
+    #[debug = "{}!"]
+    label: L,
+    #[debug(alt_format = "{:#x}")]
+    code: C,
+}
+
+#[derive(CustomDebug)]
+pub struct Register<T> {
+    #[debug(hex)]
+    value: T,
+    #[debug(binary)]
+    mask: T,
+}
+
+struct Flags(u8);
+
+impl Radix for Flags {
+    fn fmt_hex(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "flags:{:#x}", self.0)
+    }
+
+    fn fmt_binary(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "flags:{:#b}", self.0)
+    }
+}
+
+fn assert_debug<F: Debug>() {}
+
+struct Name;
+
+impl Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("name")
+    }
+}
+
+fn main() {
+    struct NotDebug;
+    assert_debug::<Packet<NotDebug, Name>>();
+
+    let packet = Packet {
+        elapsed: Duration::from_millis(1500),
+        handle: NotDebug,
+        name: Name,
+        flags: 0x1f,
+        mode: 5,
+        payload: vec![0x0a, 0xff, 0x00],
+        checksum: [0xbe, 0xef],
+        tail: &[1, 2],
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { elapsed: 1500ms, handle: <opaque>, name: name, flags: 0x1f, mode: 0b101, \
+         payload: 0x0aff00, checksum: 0xbeef, tail: [0b1, 0b10] }",
+    );
+
+    let labelled = Labelled {
+        label: Name,
+        code: 255u32,
+    };
+    assert_eq!(format!("{:?}", labelled), "Labelled { label: name!, code: 255 }");
+    assert_eq!(
+        format!("{:#?}", labelled),
+        "Labelled {\n    label: name!,\n    code: 0xff,\n}",
+    );
+
+    let register = Register {
+        value: 0xabu8,
+        mask: 6u8,
+    };
+    assert_eq!(format!("{:?}", register), "Register { value: 0xab, mask: 0b110 }");
+    let register = Register {
+        value: Flags(16),
+        mask: Flags(3),
+    };
+    assert_eq!(
+        format!("{:?}", register),
+        "Register { value: flags:0x10, mask: flags:0b11 }",
+    );
+}
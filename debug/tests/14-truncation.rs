@@ -0,0 +1,60 @@
+// Large collections and strings can be cut short in Debug output.
+//
+//   - `#[debug(max_items = N)]` prints the first N items of anything that
+//     iterates by reference, followed by `... (M more)`,
+//   - `#[debug(max_len = N)]` prints the first N characters of a string.
+//
+// The same keys on the struct set a default for every field recognisably of
+// a collection or string type. Both `{:?}` and `{:#?}` are supported.
+
+use derive_debug::CustomDebug;
+use std::collections::BTreeMap;
+
+#[derive(CustomDebug)]
+#[debug(max_items = 3, max_len = 5)]
+pub struct Log {
+    entries: Vec<u32>,
+    #[debug(max_items = 1)]
+    index: BTreeMap<&'static str, u8>,
+    message: String,
+    #[debug(max_len = 20)]
+    source: &'static str,
+    short: [u8; 2],
+    count: u64,
+}
+
+fn main() {
+    let log = Log {
+        entries: (0..50_000).collect(),
+        index: BTreeMap::from([("a", 1), ("b", 2)]),
+        message: "hello, world".to_owned(),
+        source: "main.rs",
+        short: [1, 2],
+        count: 7,
+    };
+    assert_eq!(
+        format!("{:?}", log),
+        r#"Log { entries: [0, 1, 2, ... (49997 more)], index: {"a": 1, ... (1 more)}, message: "hello"... (7 more), source: "main.rs", short: [1, 2], count: 7 }"#,
+    );
+
+    let expected = r#"Log {
+    entries: [
+        0,
+        1,
+        2,
+        ... (49997 more),
+    ],
+    index: {
+        "a": 1,
+        ... (1 more),
+    },
+    message: "hello"... (7 more),
+    source: "main.rs",
+    short: [
+        1,
+        2,
+    ],
+    count: 7,
+}"#;
+    assert_eq!(format!("{:#?}", log), expected);
+}
@@ -0,0 +1,81 @@
+// Hash-based collections iterate in an unspecified order, which makes their
+// Debug output unsuitable for snapshot tests. `#[debug(sorted)]` prints the
+// entries of a map sorted by key, or the items of a set or list in order.
+// `#[debug(sorted_collections)]` on the struct does the same for every
+// `HashMap` and `HashSet` field.
+//
+// Sorting combines with `max_items`, which then keeps the smallest entries.
+//
+// In a generic struct the items, or the keys of a map, are required to be
+// `Ord` as well as `Debug`. Map values need only `Debug`.
+
+use derive_debug::CustomDebug;
+use std::collections::{HashMap, HashSet};
+
+#[derive(CustomDebug)]
+pub struct Headers {
+    #[debug(sorted)]
+    fields: HashMap<&'static str, &'static str>,
+    #[debug(sorted, max_items = 2)]
+    ids: Vec<u32>,
+}
+
+#[derive(CustomDebug)]
+#[debug(sorted_collections)]
+pub struct Index {
+    words: HashSet<&'static str>,
+    counts: HashMap<char, usize>,
+}
+
+#[derive(CustomDebug)]
+pub struct Registry<K, V> {
+    #[debug(sorted)]
+    weights: HashMap<K, V>,
+    #[debug(sorted)]
+    retired: HashSet<K>,
+}
+
+fn main() {
+    let headers = Headers {
+        fields: HashMap::from([("host", "a"), ("accept", "b"), ("cookie", "c"), ("date", "d")]),
+        ids: vec![9, 3, 7],
+    };
+    assert_eq!(
+        format!("{:?}", headers),
+        r#"Headers { fields: {"accept": "b", "cookie": "c", "date": "d", "host": "a"}, ids: [3, 7, ... (1 more)] }"#,
+    );
+
+    let index = Index {
+        words: HashSet::from(["pear", "apple", "fig", "kiwi"]),
+        counts: HashMap::from([('z', 1), ('a', 2), ('m', 3)]),
+    };
+    assert_eq!(
+        format!("{:?}", index),
+        r#"Index { words: {"apple", "fig", "kiwi", "pear"}, counts: {'a': 2, 'm': 3, 'z': 1} }"#,
+    );
+    assert_eq!(
+        format!("{:#?}", index),
+        r#"Index {
+    words: {
+        "apple",
+        "fig",
+        "kiwi",
+        "pear",
+    },
+    counts: {
+        'a': 2,
+        'm': 3,
+        'z': 1,
+    },
+}"#,
+    );
+
+    let registry = Registry {
+        weights: HashMap::from([("b", 0.5f32), ("a", 1.5)]),
+        retired: HashSet::from(["d", "c"]),
+    };
+    assert_eq!(
+        format!("{:?}", registry),
+        r#"Registry { weights: {"a": 1.5, "b": 0.5}, retired: {"c", "d"} }"#,
+    );
+}
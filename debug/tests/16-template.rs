@@ -0,0 +1,58 @@
+// A format string on the struct, or on an enum variant, replaces the derived
+// output altogether. Placeholders name fields, or index them for tuple
+// structs, and accept the usual format specs.
+//
+//     #[derive(CustomDebug)]
+//     #[debug("Point({x}, {y})")]
+//     pub struct Point {
+//         x: f64,
+//         y: f64,
+//     }
+//
+// Fields that the template does not mention are not formatted and need no
+// bound, while the ones it does are bounded by the trait their spec selects.
+
+use derive_debug::CustomDebug;
+use std::fmt::Display;
+
+#[derive(CustomDebug)]
+#[debug("Point({x:.2}, {y:.2})")]
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(CustomDebug)]
+#[debug("#{0:02x}{1:02x}{2:02x}")]
+pub struct Rgb(u8, u8, u8);
+
+pub struct NotDebug;
+
+#[derive(CustomDebug)]
+pub enum Shape<T: Display> {
+    #[debug("circle of radius {radius}")]
+    Circle { radius: T, cache: NotDebug },
+    Square { side: T },
+    #[debug("{{empty}}")]
+    Empty,
+}
+
+fn main() {
+    let point = Point { x: 1.0, y: 2.5 };
+    assert_eq!(format!("{:?}", point), "Point(1.00, 2.50)");
+
+    let rgb = Rgb(255, 8, 0);
+    assert_eq!(format!("{:?}", rgb), "#ff0800");
+
+    let circle = Shape::Circle {
+        radius: 3,
+        cache: NotDebug,
+    };
+    assert_eq!(format!("{:?}", circle), "circle of radius 3");
+
+    let square = Shape::Square { side: 2 };
+    assert_eq!(format!("{:?}", square), "Square { side: 2 }");
+
+    let empty = Shape::<u8>::Empty;
+    assert_eq!(format!("{:?}", empty), "{empty}");
+}
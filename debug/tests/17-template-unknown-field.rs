@@ -0,0 +1,12 @@
+// Placeholders that do not name a field are reported at the template.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("Point({x}, {z})")]
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+fn main() {}
@@ -0,0 +1,66 @@
+// CustomDisplay derives `Display` from the same kind of templates, given as
+// `#[display("...")]` on a struct or on each enum variant. Unit variants
+// without one print their name, and `#[display(transparent)]` forwards to the
+// only field, keeping the formatter's width and precision.
+//
+// Fields take the same attributes under `#[display(...)]` as they do under
+// `#[debug(...)]`, such as a `bound` replacing the inferred one.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+use std::fmt::Display;
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{host}:{port}")]
+pub struct Addr {
+    host: String,
+    port: u16,
+}
+
+#[derive(CustomDisplay)]
+#[display(transparent)]
+pub struct Name(String);
+
+#[derive(CustomDisplay)]
+pub enum Error<E> {
+    #[display("cannot connect to {0}")]
+    Connect(Addr),
+    #[display("request failed after {attempts} attempts: {source}")]
+    Request {
+        attempts: u32,
+        #[display(bound = "E: Display")]
+        source: E,
+    },
+    #[display(transparent)]
+    Other(Name),
+    Timeout,
+}
+
+fn main() {
+    let addr = Addr {
+        host: "localhost".to_owned(),
+        port: 8080,
+    };
+    assert_eq!(addr.to_string(), "localhost:8080");
+    assert_eq!(
+        format!("{:?}", addr),
+        r#"Addr { host: "localhost", port: 8080 }"#,
+    );
+
+    let name = Name("db".to_owned());
+    assert_eq!(format!("{:>4}", name), "  db");
+
+    let connect = Error::<String>::Connect(addr);
+    assert_eq!(connect.to_string(), "cannot connect to localhost:8080");
+
+    let request = Error::Request {
+        attempts: 3,
+        source: "reset",
+    };
+    assert_eq!(request.to_string(), "request failed after 3 attempts: reset");
+
+    let other = Error::<String>::Other(name);
+    assert_eq!(other.to_string(), "db");
+
+    let timeout = Error::<String>::Timeout;
+    assert_eq!(timeout.to_string(), "Timeout");
+}
@@ -0,0 +1,13 @@
+// Variants with fields have no obvious Display output, so CustomDisplay asks
+// for a template rather than guessing one.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+pub enum Error {
+    #[display("not found")]
+    NotFound,
+    Io(std::io::Error),
+}
+
+fn main() {}
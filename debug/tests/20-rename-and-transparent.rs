@@ -0,0 +1,55 @@
+// Labels default to the Rust identifiers. `#[debug(rename = "...")]` changes
+// the label of a field and `#[debug(name = "...")]` the name printed for a
+// struct, so that a wrapper can present as the public type it stands for.
+//
+// `#[debug(transparent)]` on a single-field struct or variant forwards to the
+// field's own Debug impl, alternate `{:#?}` output included, while still
+// honouring the field's attributes.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(name = "Request")]
+pub struct RequestInner {
+    #[debug(rename = "method")]
+    verb: &'static str,
+    path: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Request(RequestInner);
+
+#[derive(CustomDebug)]
+pub enum Credential {
+    #[debug(transparent)]
+    User(&'static str),
+    #[debug(transparent)]
+    Token(#[debug(redact)] &'static str),
+}
+
+fn main() {
+    let request = Request(RequestInner {
+        verb: "GET",
+        path: "/",
+    });
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { method: "GET", path: "/" }"#,
+    );
+    assert_eq!(
+        format!("{:#?}", request),
+        "Request {\n    method: \"GET\",\n    path: \"/\",\n}",
+    );
+
+    let user = Credential::User("ferris");
+    assert_eq!(format!("{:?}", user), r#""ferris""#);
+
+    let token = Credential::Token("hunter2");
+    let expected = if cfg!(all(feature = "unredacted-debug", debug_assertions)) {
+        r#""hunter2""#
+    } else {
+        r#""<redacted>""#
+    };
+    assert_eq!(format!("{:?}", token), expected);
+}
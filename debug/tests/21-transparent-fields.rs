@@ -0,0 +1,14 @@
+// Forwarding needs a single field to forward to, which is then always
+// printed and so cannot be skipped.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Pair(u8, u8);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Hidden(#[debug(skip)] u8);
+
+fn main() {}
@@ -0,0 +1,86 @@
+// `#[debug(flatten)]` lists the fields of a nested struct as entries of the
+// outer one instead of nesting its output.
+//
+//     Request { id: 7, method: "GET", path: "/", body: [] }
+//
+// rather than
+//
+//     Request { id: 7, meta: Meta { method: "GET", path: "/" }, body: [] }
+//
+// The nested type has to implement `derive_debug::DebugFields`, which
+// CustomDebug does for structs with named fields. Hand-written types can
+// implement it as well. Its `visit_fields` returns whether it left any fields
+// out, and if so the outer output ends in `..` as it would for its own
+// skipped fields.
+
+use derive_debug::{CustomDebug, DebugFields};
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Meta {
+    method: &'static str,
+    path: &'static str,
+    #[debug(skip)]
+    cache: (),
+}
+
+#[derive(CustomDebug)]
+pub struct Request<M> {
+    id: u32,
+    #[debug(flatten)]
+    meta: M,
+    body: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct Envelope {
+    #[debug(flatten)]
+    request: Box<Request<Meta>>,
+    #[debug(flatten)]
+    trace: Trace,
+}
+
+pub struct Trace(u64);
+
+impl DebugFields for Trace {
+    fn visit_fields(&self, visit: &mut dyn FnMut(&'static str, &dyn Debug)) -> bool {
+        visit("trace_id", &format_args!("{:016x}", self.0));
+        false
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Traced {
+    #[debug(flatten)]
+    trace: Trace,
+}
+
+fn main() {
+    assert_eq!(
+        format!("{:?}", Traced { trace: Trace(1) }),
+        "Traced { trace_id: 0000000000000001 }",
+    );
+
+    let request = Request {
+        id: 7,
+        meta: Meta {
+            method: "GET",
+            path: "/",
+            cache: (),
+        },
+        body: Vec::new(),
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { id: 7, method: "GET", path: "/", body: [], .. }"#,
+    );
+
+    let envelope = Envelope {
+        request: Box::new(request),
+        trace: Trace(0xbeef),
+    };
+    assert_eq!(
+        format!("{:?}", envelope),
+        r#"Envelope { id: 7, method: "GET", path: "/", body: [], trace_id: 000000000000beef, .. }"#,
+    );
+}
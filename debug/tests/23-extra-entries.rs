@@ -0,0 +1,32 @@
+// Debug output often benefits from information that is derived rather than
+// stored, such as the length of a buffer or whether a connection is up.
+// `#[debug(extra(name = "...", expr = "..."))]` on the struct adds an entry
+// computed from `self` after the real fields, and may be repeated.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(extra(name = "len", expr = "self.buf.len()"))]
+#[debug(extra(name = "connected", expr = "self.is_connected()"))]
+pub struct Connection {
+    #[debug(skip)]
+    buf: Vec<u8>,
+    peer: Option<&'static str>,
+}
+
+impl Connection {
+    fn is_connected(&self) -> bool {
+        self.peer.is_some()
+    }
+}
+
+fn main() {
+    let conn = Connection {
+        buf: vec![0; 512],
+        peer: Some("10.0.0.1"),
+    };
+    assert_eq!(
+        format!("{:?}", conn),
+        r#"Connection { peer: Some("10.0.0.1"), len: 512, connected: true, .. }"#,
+    );
+}
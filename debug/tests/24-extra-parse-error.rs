@@ -0,0 +1,11 @@
+// An `expr` that does not parse is reported at its string.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(extra(name = "len", expr = "self.buf.len("))]
+pub struct Buffer {
+    buf: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+// So is an `expr` that fails to type-check.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(extra(name = "len", expr = "self.buf.length()"))]
+pub struct Buffer {
+    buf: Vec<u8>,
+}
+
+fn main() {}
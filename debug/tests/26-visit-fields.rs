@@ -0,0 +1,67 @@
+// Logging backends want key-value pairs rather than one formatted string.
+// For structs with named fields, CustomDebug also implements
+// `derive_debug::DebugFields`, whose `visit_fields` passes each entry of the
+// Debug output to a callback as a `(&'static str, &dyn Debug)` pair.
+//
+// The entries are the ones the Debug impl prints: custom formats, redaction,
+// renaming, skipping, flattening and extra entries all apply. The return
+// value says whether any fields were skipped, as `..` does in the output.
+
+use derive_debug::{CustomDebug, DebugFields};
+
+#[derive(CustomDebug)]
+pub struct Peer {
+    #[debug = "{:#06x}"]
+    port: u16,
+    host: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(extra(name = "attempts", expr = "self.retries + 1"))]
+pub struct Login {
+    #[debug(rename = "user")]
+    username: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+    #[debug(flatten)]
+    peer: Peer,
+    #[debug(skip)]
+    retries: u32,
+    #[debug = "0b{:08b}"]
+    flags: u8,
+}
+
+fn main() {
+    let login = Login {
+        username: "ferris",
+        password: "hunter2",
+        peer: Peer {
+            port: 22,
+            host: "10.0.0.1",
+        },
+        retries: 2,
+        flags: 5,
+    };
+
+    let password = if cfg!(all(feature = "unredacted-debug", debug_assertions)) {
+        r#"password="hunter2""#
+    } else {
+        r#"password="<redacted>""#
+    };
+    let mut record = Vec::new();
+    let skipped = login.visit_fields(&mut |name, value| {
+        record.push(format!("{}={:?}", name, value));
+    });
+    assert!(skipped);
+    assert_eq!(
+        record,
+        [
+            r#"user="ferris""#,
+            password,
+            "port=0x0016",
+            r#"host="10.0.0.1""#,
+            "flags=0b00000101",
+            "attempts=3",
+        ],
+    );
+}
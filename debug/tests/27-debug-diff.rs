@@ -0,0 +1,99 @@
+// When `assert_eq!` fails on two large values, comparing two walls of Debug
+// output by eye is slow. `#[derive(DebugDiff)]` implements
+// `derive_debug::DebugDiff`, whose `debug_diff` lists only the fields that
+// differ, by path:
+//
+//     config.tls.port: 443 -> 8443
+//
+// Fields whose types implement DebugDiff are compared recursively, others by
+// their Debug output with the field's `#[debug(...)]` attributes applied.
+// `assert_debug_eq!` panics with that list when two values differ.
+//
+// A flattened field's entries are compared under the parent's path, one by
+// one even when its type derives only CustomDebug.
+
+use derive_debug::{assert_debug_eq, CustomDebug, DebugDiff};
+use std::panic;
+
+#[derive(CustomDebug, DebugDiff, Clone)]
+pub struct Tls {
+    port: u16,
+    #[debug = "{:.1}"]
+    version: f32,
+}
+
+#[derive(CustomDebug, DebugDiff, Clone)]
+pub struct Config {
+    name: String,
+    tls: Tls,
+    mode: Mode,
+    #[debug(skip)]
+    generation: u64,
+}
+
+#[derive(CustomDebug, DebugDiff, Clone)]
+pub enum Mode {
+    Active { since: u32 },
+    Standby,
+}
+
+#[derive(CustomDebug, DebugDiff, Clone)]
+pub struct Server {
+    config: Config,
+    #[debug(flatten)]
+    limits: Limits,
+}
+
+#[derive(CustomDebug, Clone)]
+pub struct Limits {
+    max_connections: u32,
+    timeout_ms: u32,
+}
+
+fn main() {
+    let before = Server {
+        config: Config {
+            name: "edge".to_owned(),
+            tls: Tls {
+                port: 443,
+                version: 1.3,
+            },
+            mode: Mode::Active { since: 1 },
+            generation: 1,
+        },
+        limits: Limits {
+            max_connections: 100,
+            timeout_ms: 500,
+        },
+    };
+
+    let mut after = before.clone();
+    after.config.tls.port = 8443;
+    after.config.tls.version = 1.31;
+    after.config.mode = Mode::Active { since: 2 };
+    after.config.generation = 2;
+    after.limits.timeout_ms = 250;
+    let diffs = before.debug_diff(&after);
+    let lines: Vec<String> = diffs.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "config.tls.port: 443 -> 8443",
+            "config.mode.since: 1 -> 2",
+            "timeout_ms: 500 -> 250",
+        ],
+    );
+
+    after.config.mode = Mode::Standby;
+    let diffs = before.config.mode.debug_diff(&after.config.mode);
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].to_string(), "Active { since: 1 } -> Standby");
+
+    assert_debug_eq!(before, before.clone());
+
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| assert_debug_eq!(before.config.tls, after.config.tls));
+    let payload = result.unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert_eq!(message, "assertion failed: values differ\n    port: 443 -> 8443");
+}
@@ -0,0 +1,84 @@
+// Deep trees and cyclic `Rc<RefCell<..>>` graphs make derived Debug output
+// huge or endless. `#[debug(max_depth = N)]` on a struct or enum prints at
+// most N levels of derived values, itself included, and `..` in place of
+// anything nested deeper. Every CustomDebug value counts as a level, so the
+// limit also applies across different types.
+//
+// The depth is tracked per thread while formatting, which leaves the
+// Formatter untouched and `{:#?}` working as usual.
+
+use derive_debug::CustomDebug;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 2)]
+pub struct Tree {
+    value: u32,
+    children: Vec<Tree>,
+}
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 3)]
+pub struct Node {
+    name: &'static str,
+    next: Option<Rc<RefCell<Node>>>,
+}
+
+fn leaf(value: u32) -> Tree {
+    Tree {
+        value,
+        children: Vec::new(),
+    }
+}
+
+fn main() {
+    let tree = Tree {
+        value: 1,
+        children: vec![Tree {
+            value: 2,
+            children: vec![leaf(3)],
+        }],
+    };
+    assert_eq!(
+        format!("{:?}", tree),
+        "Tree { value: 1, children: [Tree { value: 2, children: [..] }] }",
+    );
+    assert_eq!(
+        format!("{:#?}", tree),
+        "\
+Tree {
+    value: 1,
+    children: [
+        Tree {
+            value: 2,
+            children: [
+                ..,
+            ],
+        },
+    ],
+}",
+    );
+
+    // The limit only lasts while the limited value is being formatted.
+    assert_eq!(
+        format!("{:?}", tree.children[0].children[0]),
+        "Tree { value: 3, children: [] }",
+    );
+
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        next: None,
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        next: Some(a.clone()),
+    }));
+    a.borrow_mut().next = Some(b);
+    assert_eq!(
+        format!("{:?}", a.borrow()),
+        "Node { name: \"a\", next: Some(RefCell { value: Node { name: \"b\", next: \
+         Some(RefCell { value: Node { name: \"a\", next: Some(RefCell { value: .. }) } }) } }) }",
+    );
+    a.borrow_mut().next = None;
+}
@@ -0,0 +1,13 @@
+// Misspelled keys are rejected rather than silently ignored, with the closest
+// known key suggested.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Field {
+    name: &'static str,
+    #[debug(fromat = "0b{:08b}")]
+    bitmask: u8,
+}
+
+fn main() {}
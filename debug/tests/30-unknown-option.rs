@@ -0,0 +1,11 @@
+// The same goes for the options on the struct itself.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(max_item = 3)]
+pub struct Queue {
+    jobs: Vec<u32>,
+}
+
+fn main() {}
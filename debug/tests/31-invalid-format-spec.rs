@@ -0,0 +1,15 @@
+// Format strings are checked when the derive expands, so a bad spec is
+// reported at the attribute rather than deep inside the generated code. Doc
+// comments on the field are not mistaken for debug attributes.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Field {
+    name: &'static str,
+    /// Bits set for the field.
+    #[debug = "{:q}"]
+    bitmask: u8,
+}
+
+fn main() {}
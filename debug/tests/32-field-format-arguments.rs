@@ -0,0 +1,12 @@
+// A field format is passed the field's value and nothing else.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Field {
+    name: &'static str,
+    #[debug = "{} of {}"]
+    bitmask: u8,
+}
+
+fn main() {}
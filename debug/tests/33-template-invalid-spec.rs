@@ -0,0 +1,12 @@
+// Template placeholders are checked the same way.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("Point({x:.}, {y:.2})")]
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+fn main() {}
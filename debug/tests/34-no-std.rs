@@ -0,0 +1,50 @@
+// The generated impls refer to `core` only, so the derives work in
+// `#![no_std]` crates. Here `std` is linked only for the body of `main`, which
+// leaves it out of scope for every derive above. This builds derive_debug with
+// its default features; test 44 checks it without `alloc`.
+
+#![no_std]
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugDiff};
+
+#[derive(CustomDebug, CustomDisplay, DebugDiff)]
+#[display("{id}@{addr:#x}")]
+#[debug(max_depth = 4)]
+pub struct Device {
+    id: u8,
+    #[debug = "{:#06x}"]
+    addr: u16,
+    #[debug(redact)]
+    key: u32,
+    #[debug(skip)]
+    _reserved: [u8; 2],
+}
+
+#[derive(CustomDebug)]
+pub enum State {
+    #[debug("off")]
+    Off,
+    On(Device),
+}
+
+fn main() {
+    extern crate std;
+    use std::format;
+
+    let device = Device {
+        id: 3,
+        addr: 0x40,
+        key: 1234,
+        _reserved: [0; 2],
+    };
+    let expected = if cfg!(all(feature = "unredacted-debug", debug_assertions)) {
+        "Device { id: 3, addr: 0x0040, key: 1234, .. }"
+    } else {
+        r#"Device { id: 3, addr: 0x0040, key: "<redacted>", .. }"#
+    };
+    assert_eq!(format!("{:?}", device), expected);
+    assert_eq!(format!("{}", device), "3@0x40");
+    assert_eq!(format!("{:?}", State::Off), "off");
+    assert!(device.debug_diff(&device).is_empty());
+    let _ = State::On(device);
+}
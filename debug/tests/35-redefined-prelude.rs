@@ -0,0 +1,65 @@
+// Like the builder's test of the same name: the expansion refers to every
+// item through an absolute path, so it keeps working when the caller's code
+// gives `std`, `fmt`, `Option` and other prelude names a different meaning.
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugDiff, DebugFields};
+use ::std::collections::HashMap;
+
+mod std {}
+mod fmt {}
+
+type Option = ();
+type Some = ();
+type None = ();
+type Result = ();
+type Ok = ();
+type Err = ();
+type Box = ();
+type Vec = ();
+type String = ();
+#[allow(non_camel_case_types)]
+type str = ();
+#[allow(non_camel_case_types)]
+type usize = ();
+
+#[allow(unused_macros)]
+macro_rules! write {
+    () => {};
+}
+#[allow(unused_macros)]
+macro_rules! format_args {
+    () => {};
+}
+
+#[derive(CustomDebug, CustomDisplay, DebugDiff)]
+#[display("{name} ({count})")]
+#[debug(max_depth = 2, extra(name = "empty", expr = "self.count == 0"))]
+pub struct Counter {
+    name: &'static ::core::primitive::str,
+    #[debug = "{:>3}"]
+    count: u32,
+    #[debug(sorted)]
+    tags: HashMap<u8, u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug("{0} + {1}")]
+pub struct Sum(u8, u8);
+
+fn main() {
+    let counter = Counter {
+        name: "hits",
+        count: 7,
+        tags: HashMap::new(),
+    };
+    assert_eq!(
+        format!("{:?}", counter),
+        r#"Counter { name: "hits", count:   7, tags: {}, empty: false }"#,
+    );
+    assert_eq!(format!("{}", counter), "hits (7)");
+    assert_eq!(format!("{:?}", Sum(1, 2)), "1 + 2");
+    assert!(counter.debug_diff(&counter).is_empty());
+    let mut entries = 0;
+    counter.visit_fields(&mut |_, _| entries += 1);
+    assert_eq!(entries, 4);
+}
@@ -0,0 +1,37 @@
+// Some fields read best in a compact form on one line and in a fuller one when
+// the output is pretty-printed. `#[debug(alt_format = "...")]` gives the
+// format used under `{:#?}`, while `format`, or plain Debug when it is
+// absent, still applies under `{:?}`.
+//
+// Both strings take the field as their single argument, checked the same way
+// as `format`.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Packet {
+    #[debug(format = "{:#06x}", alt_format = "{0:#06x} ({0})")]
+    id: u16,
+    #[debug(alt_format = "{:02x?}")]
+    payload: Vec<u8>,
+    ttl: u8,
+}
+
+fn main() {
+    let packet = Packet {
+        id: 258,
+        payload: vec![1, 171],
+        ttl: 64,
+    };
+
+    let compact = format!("{:?}", packet);
+    assert_eq!(compact, "Packet { id: 0x0102, payload: [1, 171], ttl: 64 }");
+
+    let pretty = format!("{:#?}", packet);
+    let expected = r#"Packet {
+    id: 0x0102 (258),
+    payload: [01, ab],
+    ttl: 64,
+}"#;
+    assert_eq!(pretty, expected);
+}
@@ -0,0 +1,69 @@
+// `#[repr(C)]` structs shared with C code hold unions, raw pointers and
+// fixed-size character buffers, none of which Debug prints usefully.
+//
+// A union prints as `Name { .. }`, since reading a field is only sound for the
+// one it currently holds. `#[debug(union_field = "...")]` names a field the
+// caller knows to be valid, which is then printed with the usual field
+// attributes.
+//
+// Raw pointer fields print their address, or `null`, and never what they
+// point to, so they need no bound on the pointee. `#[debug(cstr)]` prints a
+// byte buffer as the C string it holds, up to the first NUL.
+
+use derive_debug::CustomDebug;
+use std::ptr;
+
+#[derive(CustomDebug)]
+#[repr(C)]
+pub union Opaque {
+    int: u32,
+    float: f32,
+}
+
+#[derive(CustomDebug)]
+#[debug(union_field = "bits")]
+#[repr(C)]
+pub union Word {
+    #[debug(hex)]
+    bits: u32,
+    bytes: [u8; 4],
+}
+
+pub struct NotDebug;
+
+#[derive(CustomDebug)]
+#[repr(C)]
+pub struct Device {
+    #[debug(cstr)]
+    name: [u8; 8],
+    #[debug(cstr)]
+    serial: [u8; 4],
+    handle: *mut NotDebug,
+    parent: *const Device,
+    word: Word,
+}
+
+fn main() {
+    let opaque = Opaque { int: 1 };
+    assert_eq!(format!("{:?}", opaque), "Opaque { .. }");
+    let _ = unsafe { opaque.float };
+
+    let word = Word { bits: 0xdead_beef };
+    assert_eq!(format!("{:?}", word), "Word { bits: 0xdeadbeef, .. }");
+    let _ = unsafe { word.bytes };
+
+    let mut handle = NotDebug;
+    let handle: *mut NotDebug = &mut handle;
+    let device = Device {
+        name: *b"eth0\0\0\0\0",
+        serial: *b"A\"7\n",
+        handle,
+        parent: ptr::null(),
+        word: Word { bits: 16 },
+    };
+    let expected = format!(
+        r#"Device {{ name: "eth0", serial: "A\"7\n" (unterminated), handle: {:p}, parent: null, word: Word {{ bits: 0x10, .. }} }}"#,
+        handle,
+    );
+    assert_eq!(format!("{:?}", device), expected);
+}
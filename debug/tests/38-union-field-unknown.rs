@@ -0,0 +1,29 @@
+// The field named by `union_field` must exist on the union and must not be
+// skipped, and the option means nothing on a struct or enum.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(union_field = "bytes")]
+#[repr(C)]
+pub union Word {
+    bits: u32,
+    byte: [u8; 4],
+}
+
+#[derive(CustomDebug)]
+#[debug(union_field = "bits")]
+#[repr(C)]
+pub union Masked {
+    #[debug(skip)]
+    bits: u32,
+    byte: [u8; 4],
+}
+
+#[derive(CustomDebug)]
+#[debug(union_field = "bits")]
+pub struct Register {
+    bits: u32,
+}
+
+fn main() {}
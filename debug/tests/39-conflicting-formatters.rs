@@ -0,0 +1,43 @@
+// Attributes that each decide how a field's value is printed cannot be
+// combined, since one of them would silently override or ignore the other.
+// The error points at the attribute given second.
+//
+// `sorted` and `max_items` are the exception, since a sorted collection can
+// still be cut short, and so are `format` and `alt_format`, which apply in
+// different modes.
+
+use derive_debug::CustomDebug;
+use std::collections::HashSet;
+
+#[derive(CustomDebug)]
+pub struct Flags {
+    #[debug(hex, display)]
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Timeout {
+    #[debug(format = "{}ms")]
+    #[debug(with = "fmt_millis")]
+    millis: u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Tags {
+    #[debug(sorted, max_items = 3)]
+    names: HashSet<String>,
+    #[debug(sorted, format = "{:?}")]
+    ids: HashSet<u32>,
+}
+
+#[derive(CustomDebug)]
+pub struct Status {
+    #[debug(hex, alt_format = "{:?}!")]
+    code: u16,
+}
+
+fn fmt_millis(millis: &u64, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}ms", millis)
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// A template formats the fields it names through its own format specs, so
+// formatting attributes on those fields, or `skip`, would be silently ignored.
+// They are reported instead. Fields the template does not use may carry any
+// attribute.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("P({x}, {y})")]
+pub struct Point {
+    #[debug(hex)]
+    x: u32,
+    y: u32,
+}
+
+#[derive(CustomDebug)]
+pub enum Shape {
+    #[debug("Circle({radius:.1})")]
+    Circle {
+        #[debug(skip)]
+        radius: f64,
+    },
+    Square(f64),
+}
+
+#[derive(CustomDebug)]
+#[debug("Pair({0}, {1})")]
+pub struct Pair(#[debug(format = "{:.2}")] f64, f64);
+
+fn main() {}
@@ -0,0 +1,24 @@
+// A template or `transparent` does all of CustomDisplay's formatting, so the
+// `#[debug(...)]` keys that change how a field or struct prints are rejected
+// in `#[display(...)]` rather than ignored. Only `bound` applies to fields.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display(transparent)]
+pub struct Token(#[display(redact)] String);
+
+#[derive(CustomDisplay)]
+#[display("{x}")]
+pub struct Reading {
+    x: u32,
+    #[display(hex)]
+    raw: u32,
+}
+
+#[derive(CustomDisplay)]
+#[display("{0}")]
+#[display(name = "Id")]
+pub struct UserId(u64);
+
+fn main() {}
@@ -0,0 +1,26 @@
+// A flattened field contributes the inner struct's entries instead of its
+// own, so attributes that would format or label the field's entry cannot be
+// combined with `flatten`, in either order.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Credentials {
+    user: String,
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Login {
+    #[debug(flatten, redact)]
+    credentials: Credentials,
+}
+
+#[derive(CustomDebug)]
+pub struct Session {
+    #[debug(rename = "creds")]
+    #[debug(flatten)]
+    credentials: Credentials,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// The value a `max_depth` is given on always prints, so the smallest limit
+// is 1, which elides every derived value nested inside it.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 0)]
+pub struct Tree {
+    children: Vec<Tree>,
+}
+
+fn main() {}
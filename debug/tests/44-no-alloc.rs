@@ -0,0 +1,55 @@
+// Run by `cargo test --no-default-features`, which builds derive_debug
+// without its `alloc` feature. The derives, and every attribute outside of
+// DebugDiff and `sorted`, then need nothing beyond `core`, so a `#![no_std]`
+// crate without a global allocator can use them. As in test 34, `std` is
+// linked only for the body of `main`.
+
+#![no_std]
+
+use derive_debug::{CustomDebug, CustomDisplay, DebugFields};
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{id}")]
+pub struct Frame {
+    id: u8,
+    #[debug(hex)]
+    flags: u16,
+    #[debug(max_items = 2)]
+    payload: [u8; 3],
+    #[debug(cstr)]
+    label: [u8; 4],
+    #[debug(redact)]
+    key: u32,
+    next: *const Frame,
+}
+
+#[derive(CustomDebug)]
+#[debug("off")]
+pub struct Off;
+
+fn main() {
+    extern crate std;
+    use std::format;
+    use std::vec::Vec;
+
+    let frame = Frame {
+        id: 7,
+        flags: 0x1f,
+        payload: [1, 2, 3],
+        label: *b"rx\0\0",
+        key: 42,
+        next: core::ptr::null(),
+    };
+    let expected = if cfg!(all(feature = "unredacted-debug", debug_assertions)) {
+        r#"Frame { id: 7, flags: 0x1f, payload: [1, 2, ... (1 more)], label: "rx", key: 42, next: null }"#
+    } else {
+        r#"Frame { id: 7, flags: 0x1f, payload: [1, 2, ... (1 more)], label: "rx", key: "<redacted>", next: null }"#
+    };
+    assert_eq!(format!("{:?}", frame), expected);
+    assert_eq!(format!("{}", frame), "7");
+    assert_eq!(format!("{:?}", Off), "off");
+
+    let mut names = Vec::new();
+    frame.visit_fields(&mut |name, _| names.push(name));
+    assert_eq!(names, ["id", "flags", "payload", "label", "key", "next"]);
+}
@@ -0,0 +1,25 @@
+// Without the `alloc` feature, `#[debug(sorted)]` has nothing to sort into,
+// and without `std` there is nowhere to track `#[debug(max_depth = N)]`. Both
+// fail to compile with an error naming the feature, rather than being
+// silently ignored.
+
+#![no_std]
+
+extern crate std;
+
+use derive_debug::CustomDebug;
+use std::collections::HashMap;
+
+#[derive(CustomDebug)]
+#[debug(max_depth = 2)]
+pub struct Tree {
+    value: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Index {
+    #[debug(sorted)]
+    entries: HashMap<u32, u32>,
+}
+
+fn main() {}
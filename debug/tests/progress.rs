@@ -1,12 +1,57 @@
+#[cfg(feature = "std")]
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/01-parse.rs");
     t.pass("tests/02-impl-debug.rs");
     t.pass("tests/03-custom-format.rs");
-    //t.pass("tests/04-type-parameter.rs");
-    //t.pass("tests/05-phantom-data.rs");
-    //t.pass("tests/06-bound-trouble.rs");
-    //t.pass("tests/07-associated-type.rs");
-    //t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/04-type-parameter.rs");
+    t.pass("tests/05-phantom-data.rs");
+    t.pass("tests/06-bound-trouble.rs");
+    t.pass("tests/07-associated-type.rs");
+    t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-field-bound.rs");
+    t.pass("tests/10-enums-and-tuple-structs.rs");
+    t.pass("tests/11-skip-fields.rs");
+    t.pass("tests/12-redact.rs");
+    t.pass("tests/13-field-formatters.rs");
+    t.pass("tests/14-truncation.rs");
+    t.pass("tests/15-sorted-collections.rs");
+    t.pass("tests/16-template.rs");
+    t.compile_fail("tests/17-template-unknown-field.rs");
+    t.pass("tests/18-custom-display.rs");
+    t.compile_fail("tests/19-display-missing-template.rs");
+    t.pass("tests/20-rename-and-transparent.rs");
+    t.compile_fail("tests/21-transparent-fields.rs");
+    t.pass("tests/22-flatten.rs");
+    t.pass("tests/23-extra-entries.rs");
+    t.compile_fail("tests/24-extra-parse-error.rs");
+    t.compile_fail("tests/25-extra-type-error.rs");
+    t.pass("tests/26-visit-fields.rs");
+    t.pass("tests/27-debug-diff.rs");
+    t.pass("tests/28-max-depth.rs");
+    t.compile_fail("tests/29-unknown-field-key.rs");
+    t.compile_fail("tests/30-unknown-option.rs");
+    t.compile_fail("tests/31-invalid-format-spec.rs");
+    t.compile_fail("tests/32-field-format-arguments.rs");
+    t.compile_fail("tests/33-template-invalid-spec.rs");
+    t.pass("tests/34-no-std.rs");
+    t.pass("tests/35-redefined-prelude.rs");
+    t.pass("tests/36-alternate-format.rs");
+    t.pass("tests/37-ffi-types.rs");
+    t.compile_fail("tests/38-union-field-unknown.rs");
+    t.compile_fail("tests/39-conflicting-formatters.rs");
+    t.compile_fail("tests/40-template-field-attributes.rs");
+    t.compile_fail("tests/41-display-unsupported-attributes.rs");
+    t.compile_fail("tests/42-flatten-conflicts.rs");
+    t.compile_fail("tests/43-max-depth-zero.rs");
+}
+
+// `cargo test --no-default-features` builds derive_debug without `alloc`.
+#[cfg(not(feature = "alloc"))]
+#[test]
+fn no_alloc() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/44-no-alloc.rs");
+    t.compile_fail("tests/45-no-alloc-unsupported.rs");
 }